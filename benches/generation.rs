@@ -0,0 +1,120 @@
+//! Benchmarks for generation's hot paths.
+//!
+//! Baseline (order of magnitude, with `cargo bench` on one core of a Linux VM),
+//! so that a regression of 10x or more stands out:
+//! - `draw_pixel` (10 commits): ~1 ms
+//! - `image_to_columns` (decoding a 100x7 PNG): ~10 µs
+//! - `draw_repeating_pattern` (a full year of a 10x7 image at scale 5): ~500 ms
+//! - `auto_contrast` (every column of the graph): ~1 µs
+
+use {
+    chrono::NaiveDate,
+    contributron::{
+        GitInfo, WEEKS, WeekStart, auto_contrast, commit_tree, draw_pixel, draw_repeating_pattern,
+        image_to_columns, init_repo, year_window,
+    },
+    core::hint::black_box,
+    criterion::{Criterion, criterion_group, criterion_main},
+    std::{cell::Cell, io::Cursor},
+};
+
+fn git(dir: &tempfile::TempDir) -> GitInfo<'static, 'static, 'static> {
+    GitInfo {
+        repo: init_repo(&dir.path().join("repo"), false).unwrap(),
+        reference: "HEAD",
+        name: "Bench",
+        email: "bench@example.com",
+        author_name: "Bench",
+        author_email: "bench@example.com",
+        dry_run: false,
+        offset_minutes: 0,
+        gpg: None,
+        quiet: true,
+        messages: vec![],
+        commits_made: Cell::new(0),
+        commit_time: chrono::NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+        spread_hours: None,
+        gaussian_minutes: None,
+        jitter_secs: 0,
+        rng: None,
+        min_commits: 1,
+        max_commits: None,
+        poisson: false,
+        verbose: false,
+        content_file: None,
+        readme_template: None,
+        image_name: String::new(),
+        checkpoint: None,
+    }
+}
+
+/// Luma `(7 * x + y) * 255 / n` at `(x, y)`: a gradient with every brightness level.
+fn gradient(width: u32) -> image::GrayImage {
+    image::GrayImage::from_fn(width, 7, |x, y| {
+        image::Luma([u8::try_from((7 * x + y) * 255 / (7 * width)).unwrap()])
+    })
+}
+
+fn bench_draw_pixel(c: &mut Criterion) {
+    let dir = tempfile::tempdir().unwrap();
+    let git = git(&dir);
+    let tree = commit_tree(&git).unwrap();
+    let date = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let _ = c.bench_function("draw_pixel", |b| {
+        b.iter(|| draw_pixel(&git, &tree, black_box(10), 10, date).unwrap());
+    });
+}
+
+fn bench_image_to_columns(c: &mut Criterion) {
+    let mut png = vec![];
+    let () = gradient(100)
+        .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+        .unwrap();
+    let _ = c.bench_function("image_to_columns", |b| {
+        b.iter(|| image_to_columns(&image::load_from_memory(black_box(&png)).unwrap()));
+    });
+}
+
+fn bench_draw_repeating_pattern(c: &mut Criterion) {
+    let dir = tempfile::tempdir().unwrap();
+    let git = git(&dir);
+    let columns = image_to_columns(&gradient(10).into());
+    let dates = year_window(2024, WeekStart::Sunday).unwrap();
+    let mut group = c.benchmark_group("draw_repeating_pattern");
+    let _ = group.sample_size(10);
+    let _ = group.bench_function("year", |b| {
+        b.iter(|| {
+            draw_repeating_pattern(
+                &git,
+                &columns,
+                dates.clone(),
+                5,
+                None,
+                #[cfg(feature = "progress")]
+                None,
+            )
+            .unwrap();
+        });
+    });
+    let () = group.finish();
+}
+
+fn bench_auto_contrast(c: &mut Criterion) {
+    let columns = image_to_columns(&gradient(u32::from(WEEKS)).into());
+    let _ = c.bench_function("auto_contrast", |b| {
+        b.iter_batched_ref(
+            || columns.clone(),
+            |columns| auto_contrast(black_box(columns)),
+            criterion::BatchSize::SmallInput,
+        );
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_draw_pixel,
+    bench_image_to_columns,
+    bench_draw_repeating_pattern,
+    bench_auto_contrast,
+);
+criterion_main!(benches);
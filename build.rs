@@ -0,0 +1,78 @@
+//! Optionally, write every shell's completion script as part of the build:
+//! set `CONTRIBUTRON_COMPLETIONS_DIR` to the directory to write them to.
+//!
+//! A build script can't call the crate it builds, so this builds a second copy of `contributron`
+//! (under `OUT_DIR`, without `CONTRIBUTRON_COMPLETIONS_DIR`) and runs `contributron completions <shell>`.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// Every shell that `contributron completions` supports, with the file name its script goes in.
+const SHELLS: [(&str, &str); 5] = [
+    ("bash", "contributron.bash"),
+    ("zsh", "_contributron"),
+    ("fish", "contributron.fish"),
+    ("elvish", "contributron.elv"),
+    ("powershell", "_contributron.ps1"),
+];
+
+fn main() {
+    println!("cargo::rerun-if-env-changed=CONTRIBUTRON_COMPLETIONS_DIR");
+    let Some(dir) = env::var_os("CONTRIBUTRON_COMPLETIONS_DIR") else {
+        return;
+    };
+    let dir = PathBuf::from(dir);
+    // Otherwise, only the environment variable would ever rerun this:
+    println!("cargo::rerun-if-changed=build.rs");
+    println!("cargo::rerun-if-changed=src");
+
+    let manifest_dir = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").expect("set by Cargo"));
+    let target_dir =
+        PathBuf::from(env::var_os("OUT_DIR").expect("set by Cargo")).join("completions");
+    let cargo = env::var_os("CARGO").expect("set by Cargo");
+    let status = Command::new(cargo)
+        .args([
+            "build",
+            "--quiet",
+            "--bin",
+            "contributron",
+            "--manifest-path",
+        ])
+        .arg(manifest_dir.join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .env_remove("CONTRIBUTRON_COMPLETIONS_DIR")
+        .status()
+        .expect("couldn't run Cargo to build `contributron` for its completions");
+    assert!(
+        status.success(),
+        "building `contributron` for its completions failed ({status})"
+    );
+
+    fs::create_dir_all(&dir).unwrap_or_else(|e| panic!("couldn't create `{}`: {e}", dir.display()));
+    let binary = target_dir
+        .join("debug")
+        .join(format!("contributron{}", env::consts::EXE_SUFFIX));
+    for (shell, file) in SHELLS {
+        let () = write_completions(&binary, shell, &dir.join(file));
+    }
+}
+
+/// Run `binary completions <shell>` and write what it prints to `path`.
+fn write_completions(binary: &Path, shell: &str, path: &Path) {
+    let output = Command::new(binary)
+        .args(["completions", shell])
+        .output()
+        .unwrap_or_else(|e| panic!("couldn't run `{}`: {e}", binary.display()));
+    assert!(
+        output.status.success(),
+        "`contributron completions {shell}` failed ({}): {}",
+        output.status,
+        String::from_utf8_lossy(&output.stderr),
+    );
+    fs::write(path, output.stdout)
+        .unwrap_or_else(|e| panic!("couldn't write `{}`: {e}", path.display()));
+}
@@ -0,0 +1,11 @@
+//! Any bytes at all should either fail to decode or become columns, never panic.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(img) = image::load_from_memory(data) {
+        let _ = contributron::image_to_columns(&img);
+    }
+});
@@ -0,0 +1,801 @@
+//! Persistent defaults for command-line arguments, read from a TOML file.
+
+use {
+    crate::{
+        Align, Args, BuiltinKind, CommitArgs, ContributronError, Curve, DitherMode,
+        GrayscaleMethod, Platform, ResizeFilter, Rotation, VerifyImageArgs, WeekStart,
+    },
+    chrono::{NaiveDate, NaiveTime},
+    clap::{ArgMatches, Args as _, CommandFactory as _, FromArgMatches as _, parser::ValueSource},
+    std::{
+        collections::BTreeMap,
+        env,
+        ffi::OsString,
+        fs,
+        io::{ErrorKind, Write},
+        path::{Path, PathBuf},
+    },
+};
+
+/// Where the configuration file lives unless `--config` says otherwise.
+const DEFAULT_CONFIG: &str = ".config/contributron/config.toml";
+
+/// Subcommands that draw, each taking `Args` (and `generate`, `CommitArgs` too).
+const DRAWING: [&str; 3] = ["generate", "preview", "plan"];
+
+/// Arguments that each say what to draw, any one of which stands in for `--image`.
+const SOURCES: [&str; 8] = [
+    "image",
+    "text",
+    "noise",
+    "pattern",
+    "qr",
+    "per_day_file",
+    "from_git_log",
+    "from_plan",
+];
+
+/// Written by `contributron init-config`.
+const TEMPLATE: &str = r#"# Defaults for `contributron`.
+# Every key is the name of a command-line argument (with `_` instead of `-`),
+# and anything passed on the command line takes precedence.
+
+# Name and email of the Git contributor:
+# name = "Your Name"
+# email = "you@example.com"
+
+# UTC offset, in minutes, recorded in each commit:
+# timezone = 0
+
+# Number of commits for a white pixel:
+# scale = 10
+
+# Dithering (`floyd-steinberg` or `bayer`):
+# dither = "floyd-steinberg"
+
+# Git reference (usually a branch name):
+# git_reference = "HEAD"
+
+# Sign each commit with this GPG key:
+# gpg_key_id = "0123456789ABCDEF"
+
+# Contributor details chosen with `--profile <name>`,
+# taking precedence over everything above (but not the command line):
+# [profiles.work]
+# name = "Your Name"
+# email = "you@work.example.com"
+# timezone = -300
+# git_reference = "refs/heads/main"
+"#;
+
+/// Everything in a configuration file.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct Config {
+    /// Defaults for every argument, at the top level of the file.
+    pub defaults: PartialArgs,
+    /// Named contributor profiles, from `[profiles.<name>]`.
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// One contributor (e.g. one GitHub account), chosen with `--profile`.
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+#[non_exhaustive]
+pub struct Profile {
+    /// See `Args::name`.
+    pub name: Option<String>,
+    /// See `Args::email`.
+    pub email: Option<String>,
+    /// See `Args::timezone`.
+    pub timezone: Option<i32>,
+    /// See `Args::git_reference`.
+    pub git_reference: Option<String>,
+}
+
+/// Like `Args`, but with every field optional, as read from a configuration file.
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+#[non_exhaustive]
+pub struct PartialArgs {
+    /// See `Args::repo`.
+    pub repo: Option<PathBuf>,
+    /// See `Args::image` (though only one).
+    pub image: Option<PathBuf>,
+    /// See `Args::animate`.
+    pub animate: Option<bool>,
+    /// See `Args::text`.
+    pub text: Option<String>,
+    /// See `Args::letter_brightness`.
+    pub letter_brightness: Option<u8>,
+    /// See `Args::spacing`.
+    pub spacing: Option<u8>,
+    /// See `Args::noise`.
+    pub noise: Option<f64>,
+    /// See `Args::pattern`.
+    pub pattern: Option<BuiltinKind>,
+    /// See `Args::pattern_period`.
+    pub pattern_period: Option<u8>,
+    /// See `Args::qr`.
+    pub qr: Option<String>,
+    /// See `Args::per_day_file`.
+    pub per_day_file: Option<PathBuf>,
+    /// See `Args::from_git_log`.
+    pub from_git_log: Option<PathBuf>,
+    /// See `Args::name`.
+    pub name: Option<String>,
+    /// See `Args::email`.
+    pub email: Option<String>,
+    /// See `Args::author_name`.
+    pub author_name: Option<String>,
+    /// See `Args::author_email`.
+    pub author_email: Option<String>,
+    /// See `Args::git_reference`.
+    pub git_reference: Option<String>,
+    /// See `Args::scale`.
+    pub scale: Option<u8>,
+    /// See `Args::overwrite`.
+    pub overwrite: Option<bool>,
+    /// See `Args::append`.
+    pub append: Option<bool>,
+    /// See `Args::update`.
+    pub update: Option<bool>,
+    /// See `Args::poisson`.
+    pub poisson: Option<bool>,
+    /// See `Args::dither`.
+    pub dither: Option<DitherMode>,
+    /// See `Args::resize`.
+    pub resize: Option<bool>,
+    /// See `Args::resize_filter`.
+    pub resize_filter: Option<ResizeFilter>,
+    /// See `Args::dry_run`.
+    pub dry_run: Option<bool>,
+    /// See `Args::start_date`.
+    pub start_date: Option<NaiveDate>,
+    /// See `Args::since`.
+    pub since: Option<NaiveDate>,
+    /// See `Args::end_date`.
+    pub end_date: Option<NaiveDate>,
+    /// See `Args::allow_long_range`.
+    pub allow_long_range: Option<bool>,
+    /// See `Args::year`.
+    pub year: Option<i32>,
+    /// See `Args::week_start`.
+    pub week_start: Option<WeekStart>,
+    /// See `Args::platform`.
+    pub platform: Option<Platform>,
+    /// See `Args::timezone`.
+    pub timezone: Option<i32>,
+    /// See `Args::gpg_key_id`.
+    pub gpg_key_id: Option<String>,
+    /// See `Args::gpg_program`.
+    pub gpg_program: Option<PathBuf>,
+    /// See `Args::push`.
+    pub push: Option<String>,
+    /// See `Args::push_force`.
+    pub push_force: Option<bool>,
+    /// See `Args::tag_prefix`.
+    pub tag_prefix: Option<String>,
+    /// See `Args::tag_force`.
+    pub tag_force: Option<bool>,
+    /// See `Args::preview`.
+    pub preview: Option<bool>,
+    /// See `Args::quiet`.
+    pub quiet: Option<bool>,
+    /// See `Args::crop_to_content`.
+    pub crop_to_content: Option<bool>,
+    /// See `Args::padding`.
+    pub padding: Option<usize>,
+    /// See `Args::padding_left`.
+    pub padding_left: Option<usize>,
+    /// See `Args::padding_right`.
+    pub padding_right: Option<usize>,
+    /// See `Args::padding_rows_top`.
+    pub padding_rows_top: Option<u8>,
+    /// See `Args::padding_rows_bottom`.
+    pub padding_rows_bottom: Option<u8>,
+    /// See `Args::offset`.
+    pub offset: Option<usize>,
+    /// See `Args::align`.
+    pub align: Option<Align>,
+    /// See `Args::repeat`.
+    pub repeat: Option<usize>,
+    /// See `Args::threshold`.
+    pub threshold: Option<u8>,
+    /// See `Args::curve`.
+    pub curve: Option<Curve>,
+    /// See `Args::posterize`.
+    pub posterize: Option<u8>,
+    /// See `Args::background`.
+    pub background: Option<u8>,
+    /// See `Args::weekday_only`.
+    pub weekday_only: Option<bool>,
+    /// See `Args::weekend_only`.
+    pub weekend_only: Option<bool>,
+    /// See `Args::weekend_boost`.
+    pub weekend_boost: Option<f32>,
+    /// See `Args::invert`.
+    pub invert: Option<bool>,
+    /// See `Args::brightness`.
+    pub brightness: Option<i16>,
+    /// See `Args::contrast`.
+    pub contrast: Option<f32>,
+    /// See `Args::blur`.
+    pub blur: Option<f32>,
+    /// See `Args::grayscale_method`.
+    pub grayscale_method: Option<GrayscaleMethod>,
+    /// See `Args::alpha_threshold`.
+    pub alpha_threshold: Option<u8>,
+    /// See `Args::gamma`.
+    pub gamma: Option<f32>,
+    /// See `Args::auto_contrast`.
+    pub auto_contrast: Option<bool>,
+    /// See `Args::flip_h`.
+    pub flip_h: Option<bool>,
+    /// See `Args::flip_v`.
+    pub flip_v: Option<bool>,
+    /// See `Args::rotate`.
+    pub rotate: Option<Rotation>,
+    /// See `Args::message`.
+    pub message: Option<String>,
+    /// See `Args::message_file`.
+    pub message_file: Option<PathBuf>,
+    /// See `Args::commit_time`.
+    pub commit_time: Option<NaiveTime>,
+    /// See `Args::spread_commits`.
+    pub spread_commits: Option<bool>,
+    /// See `Args::spread_window`.
+    pub spread_window: Option<f32>,
+    /// See `Args::gaussian_time`.
+    pub gaussian_time: Option<f32>,
+    /// See `Args::jitter`.
+    pub jitter: Option<u32>,
+    /// See `Args::seed`.
+    pub seed: Option<u64>,
+    /// See `Args::max_commits`.
+    pub max_commits: Option<u8>,
+    /// See `Args::min_commits`.
+    pub min_commits: Option<u8>,
+    /// See `Args::output_json`.
+    #[cfg(feature = "output-json")]
+    pub output_json: Option<PathBuf>,
+    /// See `Args::output_csv`.
+    pub output_csv: Option<PathBuf>,
+    /// See `Args::output_svg`.
+    pub output_svg: Option<PathBuf>,
+    /// See `Args::output_ical`.
+    pub output_ical: Option<PathBuf>,
+    /// See `Args::output_preview`.
+    pub output_preview: Option<PathBuf>,
+    /// See `Args::output_grid_png`.
+    pub output_grid_png: Option<PathBuf>,
+    /// See `Args::confirm`.
+    pub confirm: Option<bool>,
+    /// See `Args::require_confirm`.
+    pub require_confirm: Option<bool>,
+    /// See `Args::yes`.
+    pub yes: Option<bool>,
+    /// See `Args::verbose`.
+    pub verbose: Option<bool>,
+    /// See `Args::stats`.
+    pub stats: Option<bool>,
+    /// See `Args::list_commit_counts`.
+    pub list_commit_counts: Option<bool>,
+    /// See `Args::print_columns`.
+    pub print_columns: Option<bool>,
+    /// See `Args::jobs`.
+    pub jobs: Option<u16>,
+    /// See `Args::in_memory`.
+    pub in_memory: Option<bool>,
+    /// See `Args::content_file`.
+    pub content_file: Option<PathBuf>,
+    /// See `Args::readme_template`.
+    pub readme_template: Option<String>,
+    /// See `Args::from_plan`.
+    #[cfg(feature = "output-json")]
+    pub from_plan: Option<PathBuf>,
+    /// See `Args::checkpoint`.
+    #[cfg(feature = "output-json")]
+    pub checkpoint: Option<PathBuf>,
+    /// See `Args::resume`.
+    #[cfg(feature = "output-json")]
+    pub resume: Option<bool>,
+    /// See `Args::force_resume`.
+    #[cfg(feature = "output-json")]
+    pub force_resume: Option<bool>,
+}
+
+impl PartialArgs {
+    /// Use every value set here as the default for the argument of the same name,
+    /// so that anything passed on the command line still takes precedence.
+    /// # Errors
+    /// If any value can't be converted back into a command-line argument.
+    #[inline]
+    pub fn apply(&self, mut cmd: clap::Command) -> Result<clap::Command, ContributronError> {
+        let table = match toml::Table::try_from(self) {
+            Ok(ok) => ok,
+            Err(e) => {
+                return Err(ContributronError::InvalidArgument(format!(
+                    "Internal error: couldn't convert the configuration back to TOML: {e}"
+                )));
+            }
+        };
+        for (key, value) in table {
+            // `preview` and `plan` don't take `CommitArgs`:
+            if cmd.get_arguments().all(|arg| arg.get_id() != key.as_str()) {
+                continue;
+            }
+            if SOURCES.contains(&key.as_str()) {
+                cmd = cmd.mut_arg("image", |arg| {
+                    arg.required(false)
+                        .required_unless_present(clap::builder::Resettable::Reset)
+                });
+            }
+            let value = match value {
+                toml::Value::String(s) => s,
+                toml::Value::Integer(_) | toml::Value::Float(_) | toml::Value::Boolean(_) => {
+                    value.to_string()
+                }
+                toml::Value::Datetime(_) | toml::Value::Array(_) | toml::Value::Table(_) => {
+                    return Err(ContributronError::InvalidArgument(format!(
+                        "Internal error: configuration key `{key}` has unsupported value {value}"
+                    )));
+                }
+            };
+            cmd = cmd.mut_arg(key, |arg| arg.default_value(value).required(false));
+        }
+        Ok(cmd)
+    }
+}
+
+/// The whole command line.
+#[derive(Debug, clap::Parser)]
+#[command(version, about, long_about = None, subcommand_required = true)]
+#[non_exhaustive]
+pub struct Cli {
+    /// What to do.
+    #[command(subcommand)]
+    pub command: Commands,
+}
+
+/// Everything the command line can ask for.
+#[derive(Debug, clap::Subcommand)]
+#[non_exhaustive]
+pub enum Commands {
+    /// Draw on a contribution graph.
+    Generate(Box<GenerateArgs>),
+    /// Print how a drawing would look (as in `generate --dry-run --preview`) without drawing it.
+    Preview(Box<PreviewArgs>),
+    /// Print the commits a drawing would make (as in `generate --dry-run`) without making them.
+    Plan(Box<PlanArgs>),
+    /// Reset the reference that the last run drew on to where it was before.
+    Undo(UndoArgs),
+    /// Print an image's dimensions, brightness, and commits (at `--scale`), and warn about anything that would stop it drawing as expected.
+    VerifyImage(VerifyImageArgs),
+    /// Write a commented template configuration file.
+    InitConfig {
+        /// Where to write it [default: `~/.config/contributron/config.toml`]
+        #[arg(long)]
+        config: Option<PathBuf>,
+    },
+    /// Print the name of every profile in a configuration file, one per line.
+    ListProfiles {
+        /// Which configuration file [default: `~/.config/contributron/config.toml`]
+        #[arg(long)]
+        config: Option<PathBuf>,
+    },
+    /// Print the graph's width and week start on each `--platform`.
+    ListPlatforms,
+    /// Print a script that completes subcommands, arguments, and their values in a shell.
+    #[command(after_long_help = COMPLETIONS_HELP)]
+    Completions {
+        /// Which shell to complete in.
+        shell: clap_complete::Shell,
+    },
+    /// Print a man page (in troff) for `contributron(1)`.
+    #[command(
+        after_long_help = "Installation:\n  contributron man > /usr/local/share/man/man1/contributron.1"
+    )]
+    Man,
+}
+
+/// Examples for the man page (see `write_man_page`): what each does, then the command line.
+const EXAMPLES: [(&str, &str); 5] = [
+    (
+        "Draw an image on a new repository's contribution graph, then push it:",
+        "contributron generate --repo art --image art.png --name 'Your Name' --email you@example.com --push git@github.com:you/art.git",
+    ),
+    (
+        "Print how some text would look, without drawing it:",
+        "contributron preview --repo art --text HELLO --name 'Your Name' --email you@example.com",
+    ),
+    (
+        "Save every commit a drawing would make as CSV:",
+        "contributron plan --repo art --image art.png --name 'Your Name' --email you@example.com --output-csv plan.csv",
+    ),
+    (
+        "Take back the last run on a repository:",
+        "contributron undo --repo art",
+    ),
+    (
+        "Install this page:",
+        "contributron man > /usr/local/share/man/man1/contributron.1",
+    ),
+];
+
+/// How to install `contributron completions`' output, shell by shell.
+const COMPLETIONS_HELP: &str = "\
+Installation (then start a new shell):
+  bash:
+    contributron completions bash > ~/.local/share/bash-completion/completions/contributron
+  zsh (to any directory in `$fpath`):
+    contributron completions zsh > \"${fpath[1]}/_contributron\"
+  fish:
+    contributron completions fish > ~/.config/fish/completions/contributron.fish
+  elvish:
+    contributron completions elvish >> ~/.config/elvish/rc.elv
+  powershell:
+    contributron completions powershell >> $PROFILE";
+
+/// Write a man page (in troff) for `contributron(1)`,
+/// with every option that draws (i.e. `generate`'s) and an `EXAMPLES` section.
+/// # Errors
+/// If writing fails.
+#[inline]
+pub fn write_man_page(out: &mut dyn Write) -> Result<(), ContributronError> {
+    let man = clap_mangen::Man::new(Cli::command());
+    let options = clap_mangen::Man::new(CommitArgs::augment_args(Args::command()));
+    let mut examples = String::from(".SH EXAMPLES\n");
+    for (description, command) in EXAMPLES {
+        // Backslashes don't occur in the examples, but hyphens need escaping to print as such:
+        examples.push_str(&format!(
+            ".PP\n{description}\n.PP\n.RS 4\n\\fB{}\\fR\n.RE\n",
+            command.replace('-', "\\-"),
+        ));
+    }
+    match man
+        .render_title(out)
+        .and_then(|()| man.render_name_section(out))
+        .and_then(|()| man.render_synopsis_section(out))
+        .and_then(|()| man.render_description_section(out))
+        .and_then(|()| options.render_options_section(out))
+        .and_then(|()| man.render_subcommands_section(out))
+        .and_then(|()| out.write_all(examples.as_bytes()))
+        .and_then(|()| man.render_version_section(out))
+    {
+        Ok(()) => Ok(()),
+        Err(source) => Err(ContributronError::Io {
+            context: "Couldn't write the man page".to_owned(),
+            source,
+        }),
+    }
+}
+
+/// Write `shell`'s completion script for the whole command line (see `Commands::Completions`).
+#[inline]
+pub fn write_completions(shell: clap_complete::Shell, out: &mut dyn Write) {
+    clap_complete::generate(shell, &mut Cli::command(), "contributron", out);
+}
+
+/// Arguments for `generate`: what to draw, and what to do with the commits.
+#[derive(Debug, clap::Args)]
+#[non_exhaustive]
+pub struct GenerateArgs {
+    /// What to draw, where, and as whom.
+    #[command(flatten)]
+    pub args: Args,
+    /// What to do with the commits once they're made.
+    #[command(flatten)]
+    pub commit: CommitArgs,
+}
+
+impl From<GenerateArgs> for Args {
+    #[inline]
+    fn from(GenerateArgs { args, commit }: GenerateArgs) -> Self {
+        Self { commit, ..args }
+    }
+}
+
+/// Arguments for `preview`, which makes no commits (so takes none of `CommitArgs`).
+#[derive(Debug, clap::Args)]
+#[non_exhaustive]
+pub struct PreviewArgs {
+    /// What to draw, where, and as whom.
+    #[command(flatten)]
+    pub args: Args,
+}
+
+impl From<PreviewArgs> for Args {
+    #[inline]
+    fn from(PreviewArgs { args }: PreviewArgs) -> Self {
+        args
+    }
+}
+
+/// Arguments for `plan`, which makes no commits (so takes none of `CommitArgs`).
+#[derive(Debug, clap::Args)]
+#[non_exhaustive]
+pub struct PlanArgs {
+    /// What to draw, where, and as whom.
+    #[command(flatten)]
+    pub args: Args,
+}
+
+impl From<PlanArgs> for Args {
+    #[inline]
+    fn from(PlanArgs { args }: PlanArgs) -> Self {
+        args
+    }
+}
+
+/// Arguments for `undo`.
+#[derive(Debug, clap::Args)]
+#[non_exhaustive]
+pub struct UndoArgs {
+    /// Repository to undo the last run on.
+    #[arg(short, long)]
+    pub repo: PathBuf,
+}
+
+/// `~/.config/contributron/config.toml`, if the home directory is known.
+#[inline]
+#[must_use]
+pub fn default_config_path() -> Option<PathBuf> {
+    env::home_dir().map(|home| home.join(DEFAULT_CONFIG))
+}
+
+/// Read a configuration file.
+/// # Errors
+/// If the file can't be read or isn't a valid configuration.
+#[inline]
+pub fn load_config(path: &Path) -> Result<Config, ContributronError> {
+    let contents = match fs::read_to_string(path) {
+        Ok(ok) => ok,
+        Err(source) => {
+            return Err(ContributronError::Io {
+                context: format!("Couldn't read `{}`", path.to_string_lossy()),
+                source,
+            });
+        }
+    };
+    let mut table: toml::Table = match toml::from_str(&contents) {
+        Ok(ok) => ok,
+        Err(source) => {
+            return Err(ContributronError::Config {
+                path: path.to_owned(),
+                source,
+            });
+        }
+    };
+    let profiles = match table.remove("profiles") {
+        None => BTreeMap::new(),
+        Some(profiles) => match profiles.try_into() {
+            Ok(ok) => ok,
+            Err(source) => {
+                return Err(ContributronError::Config {
+                    path: path.to_owned(),
+                    source,
+                });
+            }
+        },
+    };
+    match table.try_into() {
+        Ok(defaults) => Ok(Config { defaults, profiles }),
+        Err(source) => Err(ContributronError::Config {
+            path: path.to_owned(),
+            source,
+        }),
+    }
+}
+
+/// The arguments that `[profiles.<profile>]` sets, to take precedence over `config.defaults`.
+/// # Errors
+/// If there's no such profile.
+#[inline]
+pub fn load_profile(config: &Config, profile: &str) -> Result<PartialArgs, ContributronError> {
+    let Some(found) = config.profiles.get(profile) else {
+        return Err(ContributronError::UnknownProfile(profile.to_owned()));
+    };
+    let Profile {
+        ref name,
+        ref email,
+        timezone,
+        ref git_reference,
+    } = *found;
+    Ok(PartialArgs {
+        name: name.clone(),
+        email: email.clone(),
+        timezone,
+        git_reference: git_reference.clone(),
+        ..PartialArgs::default()
+    })
+}
+
+/// Write a commented template configuration file, refusing to overwrite an existing one.
+/// # Errors
+/// If the file already exists or can't be written.
+#[inline]
+pub fn init_config(path: &Path) -> Result<(), ContributronError> {
+    if let Some(parent) = path.parent() {
+        match fs::create_dir_all(parent) {
+            Ok(()) => {}
+            Err(source) => {
+                return Err(ContributronError::Io {
+                    context: format!("Couldn't ensure that `{}` exists", parent.to_string_lossy()),
+                    source,
+                });
+            }
+        }
+    }
+    let mut file = match fs::File::create_new(path) {
+        Ok(ok) => ok,
+        Err(source) => {
+            return Err(ContributronError::Io {
+                context: format!("Couldn't create `{}`", path.to_string_lossy()),
+                source,
+            });
+        }
+    };
+    match file.write_all(TEMPLATE.as_bytes()) {
+        Ok(()) => Ok(()),
+        Err(source) => Err(ContributronError::Io {
+            context: format!("Couldn't write to `{}`", path.to_string_lossy()),
+            source,
+        }),
+    }
+}
+
+/// Parse command-line arguments, filling in anything missing from the configuration file
+/// (`--config`, or else `~/.config/contributron/config.toml` if it exists).
+/// Arguments without a subcommand (as before there were any) are taken as `generate`'s, with a warning.
+/// Exits with a usage message if the arguments themselves are invalid.
+/// # Errors
+/// If the configuration file can't be read or isn't a valid configuration.
+#[inline]
+pub fn parse_args<I, T>(argv: I) -> Result<Commands, ContributronError>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    let mut argv: Vec<OsString> = argv.into_iter().map(Into::into).collect();
+    if let Some(first) = argv.get(1).and_then(|arg| arg.to_str())
+        && first.starts_with('-')
+        && !matches!(first, "-h" | "--help" | "-V" | "--version")
+    {
+        eprintln!(
+            "[warn] Drawing without a subcommand is deprecated: instead of `contributron {first} ...`, run `contributron generate {first} ...`"
+        );
+        let () = argv.insert(1, "generate".into());
+    }
+    let drawing = argv
+        .get(1)
+        .is_some_and(|arg| DRAWING.iter().any(|&name| arg == name));
+
+    let mut cmd = Cli::command();
+    if drawing {
+        // Find `--config` and `--profile` before knowing whether everything else is valid,
+        // since the configuration might supply anything that's missing:
+        let early = CommitArgs::augment_args(Args::command())
+            .ignore_errors(true)
+            .try_get_matches_from(argv.iter().take(1).chain(argv.iter().skip(2)))
+            .ok();
+        let explicit = early
+            .as_ref()
+            .and_then(|matches| matches.get_one::<PathBuf>("config").cloned());
+        let profile = early.and_then(|matches| matches.get_one::<String>("profile").cloned());
+        let config = match explicit {
+            Some(path) => Some(load_config(&path)?),
+            None => match default_config_path() {
+                Some(path) => match fs::metadata(&path) {
+                    Ok(_) => Some(load_config(&path)?),
+                    Err(e) if e.kind() == ErrorKind::NotFound => None,
+                    Err(source) => {
+                        return Err(ContributronError::Io {
+                            context: format!("Couldn't read `{}`", path.to_string_lossy()),
+                            source,
+                        });
+                    }
+                },
+                None => None,
+            },
+        };
+        if let Some(ref config) = config {
+            cmd = apply_to_drawing(cmd, &config.defaults)?;
+        }
+        if let Some(profile) = profile {
+            let Some(ref config) = config else {
+                return Err(ContributronError::UnknownProfile(profile));
+            };
+            cmd = apply_to_drawing(cmd, &load_profile(config, &profile)?)?;
+        }
+    }
+    let matches = cmd.get_matches_from(argv);
+    Ok(from_matches(&matches))
+}
+
+/// Apply `partial` (see `PartialArgs::apply`) to every subcommand that draws.
+#[inline]
+fn apply_to_drawing(
+    mut cmd: clap::Command,
+    partial: &PartialArgs,
+) -> Result<clap::Command, ContributronError> {
+    for name in DRAWING {
+        if let Some(subcommand) = cmd.find_subcommand(name) {
+            let subcommand = partial.apply(subcommand.clone())?;
+            cmd = cmd.mut_subcommand(name, |_| subcommand);
+        }
+    }
+    Ok(cmd)
+}
+
+/// Interpret already-parsed arguments, exiting with a usage message if they're invalid.
+#[inline]
+fn from_matches(matches: &ArgMatches) -> Commands {
+    let mut command = match Cli::from_arg_matches(matches) {
+        Ok(ok) => ok.command,
+        Err(e) => e.exit(),
+    };
+    let args = match command {
+        Commands::Generate(ref mut generate) => &mut generate.args,
+        Commands::Preview(ref mut preview) => &mut preview.args,
+        Commands::Plan(ref mut plan) => &mut plan.args,
+        Commands::Undo(_)
+        | Commands::VerifyImage(_)
+        | Commands::InitConfig { .. }
+        | Commands::ListProfiles { .. }
+        | Commands::ListPlatforms
+        | Commands::Completions { .. }
+        | Commands::Man => return command,
+    };
+    let Some((_, matches)) = matches.subcommand() else {
+        return command;
+    };
+    // Something to draw on the command line replaces whatever the configuration file says to draw:
+    let explicit = |id: &str| matches.value_source(id) == Some(ValueSource::CommandLine);
+    if SOURCES
+        .into_iter()
+        .any(|id| matches.contains_id(id) && explicit(id))
+    {
+        if !explicit("image") {
+            args.image = vec![];
+        }
+        if !explicit("text") {
+            args.text = None;
+        }
+        if !explicit("noise") {
+            args.noise = None;
+        }
+        if !explicit("pattern") {
+            args.pattern = None;
+        }
+        if !explicit("qr") {
+            args.qr = None;
+        }
+        if !explicit("per_day_file") {
+            args.per_day_file = None;
+        }
+        if !explicit("from_git_log") {
+            args.from_git_log = None;
+        }
+        #[cfg(feature = "output-json")]
+        if !explicit("from_plan") {
+            args.from_plan = None;
+        }
+    }
+    match command {
+        Commands::Preview(ref mut preview) => {
+            preview.args.dry_run = true;
+            preview.args.preview = true;
+        }
+        Commands::Plan(ref mut plan) => plan.args.dry_run = true,
+        Commands::Generate(_)
+        | Commands::Undo(_)
+        | Commands::VerifyImage(_)
+        | Commands::InitConfig { .. }
+        | Commands::ListProfiles { .. }
+        | Commands::ListPlatforms
+        | Commands::Completions { .. }
+        | Commands::Man => {}
+    }
+    command
+}
@@ -0,0 +1,186 @@
+//! Converting each day's pixel into a whole number of commits.
+
+use {
+    rand::Rng,
+    rand_distr::{Distribution as _, Poisson},
+};
+
+/// How to round fractional commit counts to whole numbers.
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum, serde::Deserialize, serde::Serialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum DitherMode {
+    /// Error diffusion using the classic Floyd-Steinberg coefficients.
+    FloydSteinberg,
+    /// Ordered dithering with an 8x8 Bayer matrix (keeps sharp edges sharp).
+    Bayer,
+}
+
+/// How a pixel's brightness maps to its number of commits.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    clap::ValueEnum,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Curve {
+    /// Proportionally.
+    #[default]
+    Linear,
+    /// By the square root of brightness (brightening mid-tones).
+    Sqrt,
+    /// Logarithmically (brightening mid-tones even more).
+    Log,
+    /// By the square of brightness (darkening mid-tones).
+    Quadratic,
+}
+
+/// The number of commits for `pixel` along `curve`, out of `scale` for a white pixel.
+#[inline]
+#[must_use]
+pub fn apply_curve(pixel: u8, scale: u8, curve: Curve) -> u8 {
+    let fraction = f32::from(pixel) / f32::from(u8::MAX);
+    let fraction = match curve {
+        Curve::Linear => fraction,
+        Curve::Sqrt => fraction.sqrt(),
+        Curve::Log => (f32::from(pixel) + 1.).ln() / 256_f32.ln(),
+        Curve::Quadratic => fraction.powi(2),
+    };
+    (fraction * f32::from(scale)).round() as u8
+}
+
+/// Round `pixel` to the nearest of `levels` evenly spaced brightnesses (from black to white).
+/// Fewer than two levels leave `pixel` as is.
+#[inline]
+#[must_use]
+pub fn posterize(pixel: u8, levels: u8) -> u8 {
+    if levels < 2 {
+        return pixel;
+    }
+    let steps = f32::from(levels - 1);
+    ((f32::from(pixel) / f32::from(u8::MAX) * steps).round() / steps * f32::from(u8::MAX)).round()
+        as u8
+}
+
+/// Bayer threshold matrix for ordered dithering, in `0..64`.
+pub const BAYER_8X8: [[u8; 8]; 8] = [
+    [0, 32, 8, 40, 2, 34, 10, 42],
+    [48, 16, 56, 24, 50, 18, 58, 26],
+    [12, 44, 4, 36, 14, 46, 6, 38],
+    [60, 28, 52, 20, 62, 30, 54, 22],
+    [3, 35, 11, 43, 1, 33, 9, 41],
+    [51, 19, 59, 27, 49, 17, 57, 25],
+    [15, 47, 7, 39, 13, 45, 5, 37],
+    [63, 31, 55, 23, 61, 29, 53, 21],
+];
+
+/// Convert one pixel per day (in order, one column per week)
+/// into the number of commits to make on that day,
+/// linearly mapping `0..=255` to `0..=scale`.
+#[inline]
+#[must_use]
+pub fn commit_counts(pixels: &[u8], scale: u8, dither: Option<DitherMode>) -> Vec<u8> {
+    match dither {
+        None => pixels
+            .iter()
+            .map(|&pixel| ideal_commits(pixel, scale).round() as u8)
+            .collect(),
+        Some(DitherMode::FloydSteinberg) => floyd_steinberg(pixels, scale),
+        Some(DitherMode::Bayer) => bayer(pixels, scale),
+    }
+}
+
+/// Draw each day's number of commits from a Poisson distribution
+/// whose mean is its ideal (fractional) number (so its variance grows with `scale`).
+#[inline]
+pub fn poisson_counts<R: Rng + ?Sized>(pixels: &[u8], scale: u8, rng: &mut R) -> Vec<u8> {
+    pixels
+        .iter()
+        .map(|&pixel| match Poisson::new(ideal_commits(pixel, scale)) {
+            Ok(poisson) => {
+                let sample: f32 = poisson.sample(rng);
+                sample.min(f32::from(u8::MAX)) as u8
+            }
+            // A black pixel's mean is 0, which isn't a valid Poisson distribution (but always draws 0):
+            Err(_) => 0,
+        })
+        .collect()
+}
+
+/// The ideal (fractional) number of commits for `pixel`, out of `scale` for a white pixel.
+#[inline]
+fn ideal_commits(pixel: u8, scale: u8) -> f32 {
+    f32::from(pixel) / f32::from(u8::MAX) * f32::from(scale)
+}
+
+/// Round each day's ideal (fractional) number of commits up or down
+/// depending on whether its fractional part clears the Bayer threshold
+/// at that day's position (row = day of the week, column = week).
+#[inline]
+#[must_use]
+pub fn bayer(pixels: &[u8], scale: u8) -> Vec<u8> {
+    pixels
+        .iter()
+        .enumerate()
+        .map(|(index, &pixel)| {
+            let (column, row) = (index / 7, index % 7);
+            let threshold = (f32::from(BAYER_8X8[row % 8][column % 8]) + 0.5) / 64.;
+            let exact = ideal_commits(pixel, scale);
+            let pixel_fraction = exact.fract();
+            let rounded = if pixel_fraction > threshold {
+                exact.ceil()
+            } else {
+                exact.floor()
+            };
+            rounded.clamp(0., f32::from(u8::MAX)) as u8
+        })
+        .collect()
+}
+
+/// Round each day's ideal (fractional) number of commits to an integer,
+/// diffusing the rounding error onto neighbors not yet visited.
+/// The grid is scanned row by row (i.e. all Sundays, then all Mondays, ...),
+/// so "right" means the next week and "lower" means the next day.
+#[inline]
+#[must_use]
+pub fn floyd_steinberg(pixels: &[u8], scale: u8) -> Vec<u8> {
+    let n_columns = pixels.len().div_ceil(7);
+    let mut error = vec![0_f32; 7 * n_columns];
+    let mut counts = vec![0; pixels.len()];
+
+    for row in 0..7 {
+        for column in 0..n_columns {
+            let index = 7 * column + row;
+            let Some(&pixel) = pixels.get(index) else {
+                continue;
+            };
+
+            let exact = ideal_commits(pixel, scale) + error.get(index).copied().unwrap_or(0.);
+            let rounded = exact.round().clamp(0., f32::from(u8::MAX));
+            counts[index] = rounded as u8;
+
+            let residual = exact - rounded;
+            let mut diffuse = |column: Option<usize>, row: usize, sixteenths: f32| {
+                if let Some(column) = column
+                    && row < 7
+                    && let Some(cell) = error.get_mut(7 * column + row)
+                {
+                    *cell += residual * sixteenths / 16.;
+                }
+            };
+            let () = diffuse(Some(column + 1), row, 7.);
+            let () = diffuse(column.checked_sub(1), row + 1, 3.);
+            let () = diffuse(Some(column), row + 1, 5.);
+            let () = diffuse(Some(column + 1), row + 1, 1.);
+        }
+    }
+
+    counts
+}
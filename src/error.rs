@@ -0,0 +1,312 @@
+//! Everything that can go wrong while drawing.
+
+use {
+    core::fmt,
+    std::{io, path::PathBuf},
+};
+
+/// Everything that can go wrong while drawing.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ContributronError {
+    /// Couldn't open or decode the image.
+    ImageOpen {
+        /// Path to the image.
+        path: PathBuf,
+        /// Why it couldn't be opened.
+        source: image::ImageError,
+    },
+    /// The image isn't seven pixels tall.
+    InvalidDimensions {
+        /// Path to the image.
+        path: PathBuf,
+        /// Width of the image, in pixels.
+        width: u32,
+        /// Height of the image, in pixels.
+        height: u32,
+    },
+    /// A command-line argument is out of range or otherwise unusable.
+    InvalidArgument(String),
+    /// Couldn't write an image.
+    ImageSave {
+        /// Path to the image.
+        path: PathBuf,
+        /// Why it failed.
+        source: image::ImageError,
+    },
+    /// A filesystem operation failed.
+    Io {
+        /// What we were trying to do.
+        context: String,
+        /// Why it failed.
+        source: io::Error,
+    },
+    /// Something already exists where the repository should be created.
+    RepoExists(PathBuf),
+    /// Couldn't open an existing Git repository (for `--append`).
+    RepoOpen {
+        /// Where we expected it.
+        path: PathBuf,
+        /// Why it failed.
+        source: git2::Error,
+    },
+    /// There's no record of a run to undo in this repository.
+    NothingToUndo(PathBuf),
+    /// A bare repository has no working directory to put `--content-file` or a `README.md` in.
+    WorkdirNotAvailable(PathBuf),
+    /// Couldn't initialize a Git repository.
+    RepoInit {
+        /// Where we tried to initialize it.
+        path: PathBuf,
+        /// Why it failed.
+        source: git2::Error,
+    },
+    /// The Git reference to commit to doesn't exist.
+    ReferenceNotFound {
+        /// Name of the reference.
+        reference: String,
+        /// Why it couldn't be found.
+        source: git2::Error,
+    },
+    /// Git refused to make a commit.
+    CommitFailed {
+        /// Reference we were committing to.
+        reference: String,
+        /// Message of the commit we were trying to make.
+        message: String,
+        /// Why it failed.
+        source: git2::Error,
+    },
+    /// Any other Git operation failed.
+    Git {
+        /// What we were trying to do.
+        context: String,
+        /// Why it failed.
+        source: git2::Error,
+    },
+    /// A date or time fell outside the representable range.
+    InvalidDateArithmetic(String),
+    /// GPG couldn't sign a commit (e.g. the key is not in the keyring).
+    GpgSigningFailed(String),
+    /// There's no remote with this name.
+    RemoteNotFound(String),
+    /// Couldn't push to the remote.
+    PushFailed(git2::Error),
+    /// A commit-message template has an unknown placeholder or an unmatched brace.
+    InvalidMessageTemplate(String),
+    /// Content for `--qr` couldn't be encoded (e.g. it's too long).
+    QrEncodingFailed(String),
+    /// `--profile` named a profile that the configuration file doesn't have.
+    UnknownProfile(String),
+    /// `--resume`'s repository has moved on since its checkpoint.
+    CheckpointDiverged(String),
+    /// Couldn't watch `--image` for changes.
+    WatchFailed(String),
+    /// Two arguments that can't be used together were both given.
+    ConflictingArgs(String),
+    /// Two arguments' values contradict each other.
+    InvalidArgCombination(String),
+    /// Two grids to merge aren't the same size.
+    GridDimensionMismatch {
+        /// Columns and rows of the base grid.
+        base: (usize, usize),
+        /// Columns and rows of the overlay.
+        overlay: (usize, usize),
+    },
+    /// Couldn't read or write a plan as JSON.
+    #[cfg(feature = "output-json")]
+    Json {
+        /// What we were trying to do.
+        context: String,
+        /// Why it failed.
+        source: serde_json::Error,
+    },
+    /// Couldn't write a plan as CSV.
+    Csv {
+        /// What we were trying to do.
+        context: String,
+        /// Why it failed.
+        source: csv::Error,
+    },
+    /// The user said not to go ahead.
+    Cancelled,
+    /// The configuration file isn't valid.
+    Config {
+        /// Path to the configuration file.
+        path: PathBuf,
+        /// What's wrong with it.
+        source: toml::de::Error,
+    },
+}
+
+impl fmt::Display for ContributronError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::ImageOpen {
+                ref path,
+                ref source,
+            } => write!(
+                f,
+                "Couldn't open `{}` as an image: {source}",
+                path.to_string_lossy(),
+            ),
+            Self::ImageSave {
+                ref path,
+                ref source,
+            } => write!(
+                f,
+                "Couldn't save an image to `{}`: {source}",
+                path.to_string_lossy(),
+            ),
+            Self::InvalidDimensions {
+                ref path,
+                width,
+                height,
+            } => write!(
+                f,
+                "Expected `{}` to be seven pixels tall (?x7), but it was {width}x{height} (try `--resize`)",
+                path.to_string_lossy(),
+            ),
+            Self::InvalidArgument(ref message) => write!(f, "{message}"),
+            Self::Io {
+                ref context,
+                ref source,
+            } => write!(f, "{context}: {source}"),
+            Self::RepoExists(ref path) => write!(
+                f,
+                "Repo path `{}` already exists; use `--force` to overwrite",
+                path.to_string_lossy(),
+            ),
+            Self::RepoOpen {
+                ref path,
+                ref source,
+            } => write!(
+                f,
+                "Couldn't open a Git repository at `{}`: {source}",
+                path.to_string_lossy(),
+            ),
+            Self::WorkdirNotAvailable(ref path) => write!(
+                f,
+                "The repository at `{}` is bare, so there's no working directory to put `--content-file` or a `README.md` in",
+                path.to_string_lossy(),
+            ),
+            Self::NothingToUndo(ref path) => write!(
+                f,
+                "Nothing to undo in `{}`: no run that built on earlier commits has finished there since the last undo",
+                path.to_string_lossy(),
+            ),
+            Self::RepoInit {
+                ref path,
+                ref source,
+            } => write!(
+                f,
+                "Couldn't initialize a Git repository in `{}`: {source}",
+                path.to_string_lossy(),
+            ),
+            Self::ReferenceNotFound {
+                ref reference,
+                ref source,
+            } => write!(f, "Couldn't find Git reference `{reference}`: {source}"),
+            Self::CommitFailed {
+                ref reference,
+                ref message,
+                ref source,
+            } => write!(
+                f,
+                "Couldn't commit to reference `{reference}` with message `{message}`: {source}",
+            ),
+            Self::Git {
+                ref context,
+                ref source,
+            } => write!(f, "{context}: {source}"),
+            Self::InvalidDateArithmetic(ref message) => {
+                write!(f, "Internal error: date arithmetic failed: {message}")
+            }
+            Self::GpgSigningFailed(ref message) => write!(f, "GPG signing failed: {message}"),
+            Self::RemoteNotFound(ref remote) => {
+                write!(f, "Couldn't find a remote named `{remote}`")
+            }
+            Self::PushFailed(ref source) => write!(f, "Couldn't push: {source}"),
+            Self::InvalidMessageTemplate(ref message) => {
+                write!(f, "Invalid commit-message template: {message}")
+            }
+            Self::QrEncodingFailed(ref message) => {
+                write!(f, "Couldn't encode a QR code: {message}")
+            }
+            Self::CheckpointDiverged(ref message) => write!(
+                f,
+                "The repository has changed since the checkpoint: {message}; pass `--force-resume` to resume anyway"
+            ),
+            Self::WatchFailed(ref message) => write!(f, "Couldn't watch for changes: {message}"),
+            Self::UnknownProfile(ref profile) => {
+                write!(f, "No profile named `{profile}` in the configuration file")
+            }
+            Self::ConflictingArgs(ref message) | Self::InvalidArgCombination(ref message) => {
+                write!(f, "{message}")
+            }
+            Self::GridDimensionMismatch {
+                base: (base_cols, base_rows),
+                overlay: (overlay_cols, overlay_rows),
+            } => write!(
+                f,
+                "Can't merge a {overlay_cols}x{overlay_rows} grid onto a {base_cols}x{base_rows} grid; they must be the same size",
+            ),
+            #[cfg(feature = "output-json")]
+            Self::Json {
+                ref context,
+                ref source,
+            } => write!(f, "{context}: {source}"),
+            Self::Csv {
+                ref context,
+                ref source,
+            } => write!(f, "{context}: {source}"),
+            Self::Cancelled => write!(f, "Cancelled; nothing was committed"),
+            Self::Config {
+                ref path,
+                ref source,
+            } => write!(
+                f,
+                "Invalid configuration file `{}`: {source}",
+                path.to_string_lossy(),
+            ),
+        }
+    }
+}
+
+impl core::error::Error for ContributronError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match *self {
+            Self::ImageOpen { ref source, .. } | Self::ImageSave { ref source, .. } => Some(source),
+            Self::Io { ref source, .. } => Some(source),
+            Self::Config { ref source, .. } => Some(source),
+            Self::Csv { ref source, .. } => Some(source),
+            #[cfg(feature = "output-json")]
+            Self::Json { ref source, .. } => Some(source),
+            Self::RepoInit { ref source, .. }
+            | Self::RepoOpen { ref source, .. }
+            | Self::ReferenceNotFound { ref source, .. }
+            | Self::CommitFailed { ref source, .. }
+            | Self::Git { ref source, .. }
+            | Self::PushFailed(ref source) => Some(source),
+            Self::InvalidDimensions { .. }
+            | Self::RepoExists(_)
+            | Self::WorkdirNotAvailable(_)
+            | Self::NothingToUndo(_)
+            | Self::InvalidArgument(_)
+            | Self::InvalidDateArithmetic(_)
+            | Self::GpgSigningFailed(_)
+            | Self::RemoteNotFound(_)
+            | Self::InvalidMessageTemplate(_)
+            | Self::QrEncodingFailed(_)
+            | Self::UnknownProfile(_)
+            | Self::WatchFailed(_)
+            | Self::CheckpointDiverged(_)
+            | Self::ConflictingArgs(_)
+            | Self::InvalidArgCombination(_)
+            | Self::GridDimensionMismatch { .. }
+            | Self::Cancelled => None,
+        }
+    }
+}
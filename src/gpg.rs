@@ -0,0 +1,85 @@
+//! Signing commits with GPG so that GitHub shows them as "Verified."
+
+use {
+    crate::ContributronError,
+    std::{
+        io::{ErrorKind, Write as _},
+        path::PathBuf,
+        process::{Command, Stdio},
+    },
+};
+
+/// How to sign each commit.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GpgSigner {
+    /// Fingerprint (or any other GPG user ID) of the key to sign with.
+    pub key_id: String,
+    /// GPG executable (usually just `gpg`).
+    pub program: PathBuf,
+}
+
+impl GpgSigner {
+    /// Detach-sign `buffer`, returning an ASCII-armored signature.
+    /// # Errors
+    /// If GPG can't be run or fails (e.g. when the key is not in the keyring).
+    #[inline]
+    pub fn sign(&self, buffer: &[u8]) -> Result<String, ContributronError> {
+        let mut child = match Command::new(&self.program)
+            .args(["--detach-sign", "--armor", "--local-user", &self.key_id])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+        {
+            Ok(ok) => ok,
+            Err(e) => {
+                return Err(ContributronError::GpgSigningFailed(format!(
+                    "Couldn't run `{}`: {e}",
+                    self.program.to_string_lossy(),
+                )));
+            }
+        };
+
+        // Dropping `stdin` afterward closes it, letting GPG finish:
+        if let Some(mut stdin) = child.stdin.take() {
+            match stdin.write_all(buffer) {
+                Ok(()) => {}
+                // GPG quit without reading everything, so its exit status says why:
+                Err(e) if e.kind() == ErrorKind::BrokenPipe => {}
+                Err(e) => {
+                    return Err(ContributronError::GpgSigningFailed(format!(
+                        "Couldn't pipe the commit into `{}`: {e}",
+                        self.program.to_string_lossy(),
+                    )));
+                }
+            }
+        }
+
+        let output = match child.wait_with_output() {
+            Ok(ok) => ok,
+            Err(e) => {
+                return Err(ContributronError::GpgSigningFailed(format!(
+                    "Couldn't wait for `{}` to finish: {e}",
+                    self.program.to_string_lossy(),
+                )));
+            }
+        };
+        if !output.status.success() {
+            return Err(ContributronError::GpgSigningFailed(format!(
+                "`{}` couldn't sign with key `{}` ({}): {}",
+                self.program.to_string_lossy(),
+                self.key_id,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim(),
+            )));
+        }
+
+        match String::from_utf8(output.stdout) {
+            Ok(ok) => Ok(ok),
+            Err(e) => Err(ContributronError::GpgSigningFailed(format!(
+                "`{}` produced a non-UTF-8 signature: {e}",
+                self.program.to_string_lossy(),
+            ))),
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Exporting a plan as an iCalendar file, to see it in a calendar app before committing.
+
+use {crate::CommitPlan, chrono::Days};
+
+/// One all-day event (`VEVENT`) per day of `plan` with any commits,
+/// summarized by its first commit's message and described by its number of commits.
+#[inline]
+#[must_use]
+pub fn render_ical(plan: &CommitPlan) -> String {
+    let stamp = plan.generated_at.format("%Y%m%dT%H%M%SZ");
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_owned(),
+        "VERSION:2.0".to_owned(),
+        "PRODID:-//contributron//EN".to_owned(),
+        "CALSCALE:GREGORIAN".to_owned(),
+    ];
+    for entry in &plan.entries {
+        if entry.commit_count == 0 {
+            continue;
+        }
+        let end = entry
+            .date
+            .checked_add_days(Days::new(1))
+            .unwrap_or(entry.date);
+        let summary = entry.messages.first().map_or("", String::as_str);
+        let plural = if entry.commit_count == 1 { "" } else { "s" };
+        lines.extend([
+            "BEGIN:VEVENT".to_owned(),
+            format!("UID:{}@contributron", entry.date.format("%Y%m%d")),
+            format!("DTSTAMP:{stamp}"),
+            format!("DTSTART;VALUE=DATE:{}", entry.date.format("%Y%m%d")),
+            format!("DTEND;VALUE=DATE:{}", end.format("%Y%m%d")),
+            format!("SUMMARY:{}", escape(summary)),
+            format!("DESCRIPTION:{} commit{plural}", entry.commit_count),
+            "END:VEVENT".to_owned(),
+        ]);
+    }
+    lines.push("END:VCALENDAR".to_owned());
+    let mut ical = String::new();
+    for line in lines {
+        let () = fold(&mut ical, &line);
+    }
+    ical
+}
+
+/// Escape `text` for an iCalendar text value.
+#[inline]
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                let () = escaped.push('\\');
+                let () = escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Append `line` to `ical`, folded (as iCalendar requires) into lines of at most 75 bytes,
+/// each continuation starting with a space, and each ending with CRLF.
+#[inline]
+fn fold(ical: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            let () = ical.push_str("\r\n ");
+            width = 1;
+        }
+        let () = ical.push(c);
+        width += c.len_utf8();
+    }
+    let () = ical.push_str("\r\n");
+}
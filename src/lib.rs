@@ -0,0 +1,3732 @@
+mod config;
+mod dither;
+mod error;
+mod gpg;
+mod ical;
+mod message;
+mod pattern;
+mod plan;
+mod platform;
+mod preview;
+mod push;
+mod text;
+mod verify;
+mod watch;
+mod workflow;
+
+pub use {
+    config::{
+        Cli, Commands, Config, GenerateArgs, PartialArgs, PlanArgs, PreviewArgs, Profile, UndoArgs,
+        default_config_path, init_config, load_config, load_profile, parse_args, write_completions,
+        write_man_page,
+    },
+    dither::{
+        BAYER_8X8, Curve, DitherMode, apply_curve, bayer, commit_counts, floyd_steinberg,
+        poisson_counts, posterize,
+    },
+    error::ContributronError,
+    gpg::GpgSigner,
+    ical::render_ical,
+    message::{DEFAULT_MESSAGE, choose_messages, format_message, load_messages, render_readme},
+    pattern::{
+        BuiltinKind, BuiltinPattern, ImagePattern, NoisePattern, PerDayPattern, builtin_pattern,
+        qr_to_columns,
+    },
+    plan::{
+        Checkpoint, CommitEntry, CommitPlan, GridStats, commit_count_table, plan, plan_per_day,
+        statistics, write_plan_csv,
+    },
+    platform::{Platform, PlatformConfig, WeekStart, platform_table},
+    preview::{
+        AsciiStyle, plan_grid, preview_grid, render_columns, render_preview, render_preview_png,
+        render_svg,
+    },
+    push::push,
+    text::{FONT_5X7, GLYPH_WIDTH, TextPattern, text_to_columns},
+    verify::{ImageReport, VerifyImageArgs, verify_image},
+    watch::watch,
+    workflow::render_workflow,
+};
+
+#[cfg(feature = "output-json")]
+pub use plan::{read_checkpoint, read_plan, write_checkpoint, write_plan};
+
+use {
+    chrono::{
+        DateTime, Datelike, Days, FixedOffset, NaiveDate, NaiveTime, TimeDelta, Timelike as _, Utc,
+    },
+    image::{
+        DynamicImage, GenericImageView, GrayAlphaImage, GrayImage, Luma, LumaA, Pixel, Rgb,
+        imageops::FilterType,
+    },
+    rand::{Rng as _, RngCore as _, SeedableRng as _, rngs::SmallRng},
+    rand_distr::{Distribution as _, Normal},
+    rayon::iter::{IntoParallelIterator as _, ParallelIterator as _},
+    std::{
+        cell::{Cell, RefCell},
+        collections::HashMap,
+        fs,
+        io::{self, ErrorKind, IsTerminal as _, Write as _},
+        iter,
+        ops::RangeInclusive,
+        path::{self, Path, PathBuf},
+    },
+};
+
+/// Number of days drawn on GitHub (53 weeks, the width of its contribution graph).
+pub const DAYS: u16 = const { 7 * WEEKS };
+
+/// Number of columns in GitHub's contribution graph (see `PlatformConfig::weeks` for other platforms').
+pub const WEEKS: u16 = 53;
+
+/// Anything that can be drawn: a grid of brightness, one column per week (see `ContributionGrid`),
+/// repeated as needed to fill the date range.
+pub trait PatternSource {
+    /// Every column of one copy of the pattern.
+    fn columns(&self) -> ContributionGrid;
+}
+
+/// Brightness of every cell of a pattern, one `Vec` per column (top to bottom),
+/// each `rows` long.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ContributionGrid {
+    /// Brightness of each cell, indexed by column then row.
+    pub cells: Vec<Vec<u8>>,
+    /// Number of columns (i.e. `cells.len()`).
+    pub cols: usize,
+    /// Number of rows in each column.
+    pub rows: usize,
+}
+
+impl ContributionGrid {
+    /// A blank (all-black) grid.
+    #[inline]
+    #[must_use]
+    pub fn new(cols: usize, rows: usize) -> Self {
+        Self {
+            cells: vec![vec![0; rows]; cols],
+            cols,
+            rows,
+        }
+    }
+
+    /// A grid of `columns` (left to right), each cut off or padded with black to `rows` long.
+    #[inline]
+    #[must_use]
+    pub fn from_columns(columns: Vec<Vec<u8>>, rows: usize) -> Self {
+        let cells: Vec<Vec<u8>> = columns
+            .into_iter()
+            .map(|mut column| {
+                let () = column.resize(rows, 0);
+                column
+            })
+            .collect();
+        Self {
+            cols: cells.len(),
+            cells,
+            rows,
+        }
+    }
+
+    /// Read every pixel of an image (of any height, unlike `image_to_columns`), one column per pixel column.
+    /// # Errors
+    /// If the image is too big to index.
+    #[inline]
+    pub fn from_image(img: &DynamicImage) -> Result<Self, ContributronError> {
+        let (width, height) = img.dimensions();
+        let (Ok(cols), Ok(rows)) = (usize::try_from(width), usize::try_from(height)) else {
+            return Err(ContributronError::InvalidArgument(format!(
+                "A {width}x{height} image is too big to draw"
+            )));
+        };
+        let cells = (0..width)
+            .map(|x| {
+                (0..height)
+                    .map(|y| {
+                        let [luma] = img.get_pixel(x, y).to_luma().0;
+                        luma
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(Self { cells, cols, rows })
+    }
+
+    /// Every column, left to right.
+    #[inline]
+    pub fn iter_columns(&self) -> impl Iterator<Item = &[u8]> {
+        self.cells.iter().map(Vec::as_slice)
+    }
+
+    /// Repeat the columns of `grid` until there are `target_cols` of them, truncating the last copy
+    /// (or, if `grid` has no columns, `target_cols` blank columns).
+    #[inline]
+    #[must_use]
+    pub fn tile(grid: &Self, target_cols: usize) -> Self {
+        let cells = if grid.cells.is_empty() {
+            vec![vec![0; grid.rows]; target_cols]
+        } else {
+            grid.cells
+                .iter()
+                .cycle()
+                .take(target_cols)
+                .cloned()
+                .collect()
+        };
+        Self {
+            cells,
+            cols: target_cols,
+            rows: grid.rows,
+        }
+    }
+
+    /// Draw `grid` as a grayscale image, one pixel per cell (the inverse of `from_image`).
+    #[inline]
+    #[must_use]
+    pub fn to_image(grid: &Self) -> GrayImage {
+        let width = u32::try_from(grid.cols).unwrap_or(u32::MAX);
+        let height = u32::try_from(grid.rows).unwrap_or(u32::MAX);
+        GrayImage::from_fn(width, height, |x, y| {
+            Luma([grid.get(x as usize, y as usize)])
+        })
+    }
+
+    /// Brightness of the cell at `col` and `row` (or black if that's outside the grid).
+    #[inline]
+    #[must_use]
+    pub fn get(&self, col: usize, row: usize) -> u8 {
+        self.cells
+            .get(col)
+            .and_then(|column| column.get(row))
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+/// How `ContributionGrid::merge` combines each cell of an overlay with the cell beneath it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MergeMode {
+    /// The sum (at most white).
+    Add,
+    /// The brighter of the two.
+    Max,
+    /// The darker of the two.
+    Min,
+    /// The product, as fractions of white (only bright where both are).
+    Multiply,
+    /// The inverse of the product of the inverses (dark only where both are).
+    Screen,
+}
+
+impl ContributionGrid {
+    /// Combine `overlay` with `base`, cell by cell (e.g. text over a noisy background with `MergeMode::Max`).
+    /// # Errors
+    /// If the grids aren't the same size.
+    #[inline]
+    pub fn merge(base: &Self, overlay: &Self, mode: MergeMode) -> Result<Self, ContributronError> {
+        if (base.cols, base.rows) != (overlay.cols, overlay.rows) {
+            return Err(ContributronError::GridDimensionMismatch {
+                base: (base.cols, base.rows),
+                overlay: (overlay.cols, overlay.rows),
+            });
+        }
+        let cells = base
+            .iter_columns()
+            .zip(overlay.iter_columns())
+            .map(|(below, above)| {
+                below
+                    .iter()
+                    .zip(above)
+                    .map(|(&a, &b)| {
+                        let (wide_a, wide_b) = (u16::from(a), u16::from(b));
+                        match mode {
+                            MergeMode::Add => a.saturating_add(b),
+                            MergeMode::Max => a.max(b),
+                            MergeMode::Min => a.min(b),
+                            MergeMode::Multiply => (wide_a * wide_b / 255) as u8,
+                            MergeMode::Screen => {
+                                (255 - (255 - wide_a) * (255 - wide_b) / 255) as u8
+                            }
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(Self {
+            cells,
+            cols: base.cols,
+            rows: base.rows,
+        })
+    }
+}
+
+impl From<Vec<[u8; 7]>> for ContributionGrid {
+    #[inline]
+    fn from(columns: Vec<[u8; 7]>) -> Self {
+        Self::from_columns(columns.into_iter().map(Vec::from).collect(), 7)
+    }
+}
+
+/// Arguments that say where to draw and as whom.
+#[derive(Clone, Debug, clap::Args, serde::Serialize)]
+pub struct CommonArgs {
+    /// Path (to be created) to hold the fake Git repository
+    #[arg(short, long)]
+    pub repo: PathBuf,
+    /// Name of the Git contributor (e.g. your name).
+    #[arg(short, long)]
+    pub name: String,
+    /// Email of the Git contributor (e.g. your email).
+    #[arg(short, long)]
+    pub email: String,
+}
+
+/// Arguments that only matter when commits are actually made, so only `generate` takes them.
+#[derive(Clone, Debug, clap::Args, serde::Serialize)]
+pub struct CommitArgs {
+    /// Sign each commit with this GPG key (so GitHub shows it as "Verified").
+    #[arg(long)]
+    pub gpg_key_id: Option<String>,
+    /// GPG executable used by `--gpg-key-id`.
+    #[arg(long, default_value = "gpg")]
+    pub gpg_program: PathBuf,
+    /// After drawing, push to this remote (a remote's name or a URL).
+    #[arg(long, num_args = 0..=1, default_missing_value = "origin")]
+    pub push: Option<String>,
+    /// Force-push (overwriting the remote's history) instead of pushing normally.
+    #[arg(long, default_value_t = false, requires = "push")]
+    pub push_force: bool,
+    /// After drawing, tag the first and last new commits `<TAG_PREFIX>-start` and `<TAG_PREFIX>-end`.
+    #[arg(long)]
+    pub tag_prefix: Option<String>,
+    /// Move any existing tags named by `--tag-prefix` instead of failing.
+    #[arg(long, default_value_t = false, requires = "tag_prefix")]
+    pub tag_force: bool,
+    /// Split the days into this many parts, commit to each (in its own thread) on a branch `part-{i}`,
+    /// then copy their commits in order onto `--git-reference` and delete the parts.
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+    pub jobs: u16,
+    /// Keep every new Git object in memory until the end, then write them all at once as one pack
+    /// (much faster than one file per object for big runs, but lost entirely if interrupted,
+    /// so it can't be used with `--checkpoint`).
+    #[arg(long, default_value_t = false)]
+    pub in_memory: bool,
+}
+
+impl Default for CommitArgs {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            gpg_key_id: None,
+            gpg_program: PathBuf::from("gpg"),
+            push: None,
+            push_force: false,
+            tag_prefix: None,
+            tag_force: false,
+            jobs: 1,
+            in_memory: false,
+        }
+    }
+}
+
+/// Everything `generate`, `preview`, and `plan` take, which differ only in how far they go
+/// (`preview` and `plan` leave `commit` at its defaults).
+#[derive(Clone, Debug, clap::Parser, serde::Serialize)]
+#[command(version, about, long_about = None)]
+pub struct Args {
+    /// Where to draw and as whom.
+    #[command(flatten)]
+    #[serde(flatten)]
+    pub common: CommonArgs,
+    /// Path to the image to draw (required to be 7 pixels tall; color is converted to grayscale),
+    /// or with `--animate`, one image per year
+    #[cfg_attr(
+        feature = "output-json",
+        arg(short, long, num_args = 1.., required_unless_present_any = ["from_plan", "text", "noise", "pattern", "qr", "per_day_file", "from_git_log"])
+    )]
+    #[cfg_attr(
+        not(feature = "output-json"),
+        arg(short, long, num_args = 1.., required_unless_present_any = ["text", "noise", "pattern", "qr", "per_day_file", "from_git_log"])
+    )]
+    pub image: Vec<PathBuf>,
+    /// Draw each calendar year (from the week of January 1) with the next `--image`,
+    /// in the order given and starting over if there are more years than images
+    /// (e.g. `--year`-sized graphs across `--start-date 2020-01-01 --end-date 2023-12-31 --allow-long-range`)
+    #[arg(long, default_value_t = false)]
+    pub animate: bool,
+    /// Write this text (in a 5x7 pixel font) instead of drawing an image
+    #[arg(long, conflicts_with = "image")]
+    pub text: Option<String>,
+    /// Brightness (luma) of each letter's pixels, with `--text` (before `--scale`) [default: 200]
+    #[arg(long)]
+    pub letter_brightness: Option<u8>,
+    /// Number of blank columns between letters, with `--text` [default: 1]
+    #[arg(long)]
+    pub spacing: Option<u8>,
+    /// Draw Perlin noise (seeded by `--seed`) instead of an image, sampled at this frequency:
+    /// low values (e.g. 0.1) make large, smooth blobs; high values (e.g. 2) make fine grain
+    #[arg(long, conflicts_with_all = ["image", "text"])]
+    pub noise: Option<f64>,
+    /// Draw a built-in pattern (as wide as the contribution graph) instead of an image
+    #[arg(long, value_enum, conflicts_with_all = ["image", "text", "noise"])]
+    pub pattern: Option<BuiltinKind>,
+    /// Width of each square or stripe, in cells, with `--pattern` [default: 1]
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..))]
+    pub pattern_period: Option<u8>,
+    /// Draw this text or URL as a QR code instead of an image
+    /// (shrunk to seven rows, so only its texture survives: it won't scan)
+    #[arg(long, conflicts_with_all = ["image", "text", "noise", "pattern"])]
+    pub qr: Option<String>,
+    /// Read exactly how many commits to make on each day from a CSV file with columns `date,count`
+    /// instead of drawing an image (making none on any day it doesn't list)
+    #[arg(long, conflicts_with_all = ["image", "text", "noise", "pattern", "qr"])]
+    pub per_day_file: Option<PathBuf>,
+    /// Mirror the activity of another Git repository (e.g. a private one) instead of drawing an image:
+    /// count its commits on each day, with the busiest day getting `--scale` commits
+    #[arg(long, conflicts_with_all = ["image", "text", "noise", "pattern", "qr", "per_day_file"])]
+    pub from_git_log: Option<PathBuf>,
+    /// Name of each commit's author, if not the committer
+    /// [default: `--name`]
+    #[arg(long)]
+    pub author_name: Option<String>,
+    /// Email of each commit's author, if not the committer
+    /// [default: `--email`]
+    #[arg(long)]
+    pub author_email: Option<String>,
+    /// Git reference (usually a branch name).
+    #[arg(short, long, default_value = "HEAD")]
+    pub git_reference: String,
+    /// Number of commits for a white pixel, with darker pixels scaled linearly
+    /// (so `--scale 1` draws in black and white).
+    #[arg(
+        short,
+        long,
+        default_value_t = 10,
+        value_parser = clap::value_parser!(u8).range(1..),
+        visible_alias = "brightness-levels",
+        short_alias = 'b',
+    )]
+    pub scale: u8,
+    /// Whether to overwrite an existing folder if one exists.
+    #[arg(
+        short,
+        long,
+        default_value_t = false,
+        visible_alias = "force",
+        short_alias = 'f'
+    )]
+    pub overwrite: bool,
+    /// Add commits on top of an existing repository's history instead of creating a new one,
+    /// starting the day after its last commit.
+    #[arg(long, default_value_t = false, conflicts_with = "overwrite")]
+    pub append: bool,
+    /// Add commits to an existing repository's history (as with `--append`), but on every day drawn,
+    /// making only as many as each day is missing (e.g. after editing the image).
+    #[arg(long, default_value_t = false, conflicts_with_all = ["overwrite", "append"])]
+    pub update: bool,
+    /// Dithering to smooth gradients that would otherwise band into harsh steps.
+    #[arg(long, value_enum)]
+    pub dither: Option<DitherMode>,
+    /// Draw each day's number of commits at random from a Poisson distribution
+    /// with the number it would otherwise get as its mean (reproducibly with `--seed`),
+    /// for the noise of real activity; the higher `--scale`, the noisier
+    /// (cap the long tail with `--max-commits`).
+    /// `--preview` and every `--output-*` file show the very counts drawn, even without `--seed`
+    #[arg(long, default_value_t = false, conflicts_with = "dither")]
+    pub poisson: bool,
+    /// Resize the image to seven pixels tall (preserving aspect ratio) instead of rejecting it.
+    #[arg(long, default_value_t = false)]
+    pub resize: bool,
+    /// Filter used by `--resize`: `nearest` keeps pixel art crisp;
+    /// `lanczos3` is recommended for photographic sources.
+    #[arg(long, value_enum, default_value_t = ResizeFilter::Nearest)]
+    pub resize_filter: ResizeFilter,
+    /// Print the planned commits (as tab-separated `date pixel index total`)
+    /// without creating a repository or writing any Git objects.
+    #[arg(short, long, default_value_t = false)]
+    pub dry_run: bool,
+    /// First day to draw (YYYY-MM-DD), rounded down to the start of a week (see `--week-start`)
+    /// [default: the graph's width (53 weeks on GitHub) before `--end-date`]
+    #[arg(long)]
+    pub start_date: Option<NaiveDate>,
+    /// Start drawing from this past day (YYYY-MM-DD) instead,
+    /// rounded down to the start of its week.
+    #[arg(long, conflicts_with = "start_date")]
+    pub since: Option<NaiveDate>,
+    /// Last day to draw (YYYY-MM-DD)
+    /// [default: the graph's width (see `--platform`) after `--start-date`, or else the start of this week]
+    #[arg(long)]
+    pub end_date: Option<NaiveDate>,
+    /// Allow `--start-date` and `--end-date` to span more than the graph's width (see `--platform`).
+    #[arg(long, default_value_t = false)]
+    pub allow_long_range: bool,
+    /// Draw on this calendar year's graph (from the start of the week of January 1
+    /// to the end of the week of December 31), with `--start-date` and `--end-date`
+    /// (if any) narrowing it down.
+    #[arg(long, value_parser = clap::value_parser!(i32).range(2008..))]
+    pub year: Option<i32>,
+    /// Which day of the week is the top row of the graph (and starts each column)
+    /// [default: `--platform`'s, i.e. Sunday on GitHub and Monday on GitLab]
+    #[arg(long, value_enum)]
+    pub week_start: Option<WeekStart>,
+    /// Where the graph will be shown, which decides its width and `--week-start` (unless that's given);
+    /// see `contributron list-platforms`.
+    #[arg(long, value_enum, default_value_t = Platform::GitHub)]
+    pub platform: Platform,
+    /// UTC offset in minutes (e.g. `-480` for UTC-8 or `+330` for IST) recorded in each commit.
+    /// GitHub credits each commit to its calendar day in this timezone,
+    /// so e.g. a commit at 01:00 UTC with `--timezone -300`
+    /// appears on the previous day.
+    #[arg(long, default_value_t = 0, allow_negative_numbers = true, value_parser = clap::value_parser!(i32).range(-720..=840))]
+    pub timezone: i32,
+    /// What to do with the commits once they're made (only `generate` takes these; see `GenerateArgs`).
+    #[arg(skip)]
+    #[serde(flatten)]
+    pub commit: CommitArgs,
+    /// Print the planned contribution graph before committing
+    /// (only the days left to draw after `--append`, `--update`, or `--resume`),
+    /// and, with `--dry-run`, exit right afterward.
+    #[arg(long, default_value_t = false)]
+    pub preview: bool,
+    /// Print nothing but errors and warnings (to standard error):
+    /// no progress, preview, or dry-run output.
+    /// Files requested with `--output-*` (even `-` for standard output) are still written.
+    #[arg(short, long, default_value_t = false)]
+    pub quiet: bool,
+    /// TOML file of defaults for any of these arguments
+    /// [default: `~/.config/contributron/config.toml`, if it exists]
+    #[arg(long)]
+    #[serde(skip)]
+    pub config: Option<PathBuf>,
+    /// Take `name`, `email`, `timezone`, and `git_reference` from `[profiles.<PROFILE>]`
+    /// in the configuration file (though any given here still take precedence).
+    #[arg(long)]
+    #[serde(skip)]
+    pub profile: Option<String>,
+    /// Drop blank (all-black) columns from both sides of the image
+    /// (e.g. a transparent border), before `--offset` or `--align`.
+    #[arg(long, default_value_t = false)]
+    pub crop_to_content: bool,
+    /// Number of blank columns to add on each side of the image, after `--crop-to-content`
+    /// (overridden on either side by `--padding-left` or `--padding-right`).
+    #[arg(long)]
+    pub padding: Option<usize>,
+    /// Number of blank columns to add before the image [default: `--padding`, or 0]
+    #[arg(long)]
+    pub padding_left: Option<usize>,
+    /// Number of blank columns to add after the image [default: `--padding`, or 0]
+    #[arg(long)]
+    pub padding_right: Option<usize>,
+    /// Black out this many rows (days) at the top of every column, starting from the first day of the week.
+    #[arg(long, default_value_t = 0, value_parser = clap::value_parser!(u8).range(..=7))]
+    pub padding_rows_top: u8,
+    /// Black out this many rows (days) at the bottom of every column, starting from the last day of the week.
+    #[arg(long, default_value_t = 0, value_parser = clap::value_parser!(u8).range(..=7))]
+    pub padding_rows_bottom: u8,
+    /// Number of blank columns (weeks) before the image.
+    #[arg(long, conflicts_with = "align")]
+    pub offset: Option<usize>,
+    /// Position the image within the graph instead of passing `--offset`.
+    #[arg(long, value_enum)]
+    pub align: Option<Align>,
+    /// Draw exactly this many copies of the image (each followed by a blank column),
+    /// then leave the rest of the graph blank; 0 repeats it endlessly.
+    #[arg(long, default_value_t = 0)]
+    pub repeat: usize,
+    /// Black out every pixel darker than this (e.g. a photograph's not-quite-black background)
+    #[arg(long, default_value_t = 0)]
+    pub threshold: u8,
+    /// How brightness maps to commits (`sqrt` and `log` brighten mid-tones; `quadratic` darkens them)
+    #[arg(long, value_enum, default_value_t = Curve::Linear)]
+    pub curve: Curve,
+    /// Round each pixel to the nearest of this many evenly spaced brightnesses
+    /// (e.g. 5 for the contribution graph's five shades; try with `--dither`)
+    #[arg(long, value_parser = clap::value_parser!(u8).range(2..))]
+    pub posterize: Option<u8>,
+    /// Brightness of every otherwise-black cell, for a lightly active background behind the image;
+    /// keep it below the image's dimmest lit pixels, or they'll blend in.
+    /// It's filled in after every other change to the image (including `--weekend-boost`),
+    /// except on days left blank by `--weekday-only` or `--weekend-only`,
+    /// and then scaled (and dithered) like any other pixel, so each day gets at least `--min-commits` commits.
+    #[arg(long, default_value_t = 0)]
+    pub background: u8,
+    /// Leave every Sunday and Saturday blank, for a Monday-to-Friday work schedule.
+    #[arg(long, default_value_t = false)]
+    pub weekday_only: bool,
+    /// Leave every Monday through Friday blank, drawing only on weekends.
+    #[arg(long, default_value_t = false, conflicts_with = "weekday_only")]
+    pub weekend_only: bool,
+    /// Multiply the brightness of every Sunday and Saturday by this much (up to white),
+    /// for an "I work a lot on weekends" look; from 0 (exclusive) to 10.
+    #[arg(long, default_value_t = 1.)]
+    pub weekend_boost: f32,
+    /// Swap black and white (e.g. for images drawn white-on-black), before `--auto-contrast`, `--threshold`, and `--gamma`.
+    #[arg(long, default_value_t = false)]
+    pub invert: bool,
+    /// Brighten (positive) or darken (negative) the image, after resizing.
+    #[arg(long, default_value_t = 0, allow_negative_numbers = true, value_parser = clap::value_parser!(i16).range(-128..=127))]
+    pub brightness: i16,
+    /// Increase (positive) or decrease (negative) the image's contrast, after `--brightness`.
+    #[arg(long, default_value_t = 0., allow_negative_numbers = true)]
+    pub contrast: f32,
+    /// Gaussian blur (with this standard deviation, in pixels) after resizing,
+    /// which softens isolated pixels that would otherwise become stray commits.
+    #[arg(long, num_args = 0..=1, default_missing_value = "0.5")]
+    pub blur: Option<f32>,
+    /// How to convert a color image to grayscale.
+    #[arg(long, value_enum, default_value_t = GrayscaleMethod::Luminosity)]
+    pub grayscale_method: GrayscaleMethod,
+    /// Treat pixels less opaque than this as black (i.e. no commits).
+    #[arg(long, default_value_t = 128)]
+    pub alpha_threshold: u8,
+    /// Gamma correction, `out = 255 * (in / 255) ^ gamma`:
+    /// below 1 brightens shadows, and above 1 darkens them (reasonably `0.2..5`).
+    #[arg(long, default_value_t = 1., allow_negative_numbers = true)]
+    pub gamma: f32,
+    /// Stretch the image's darkest pixel to black and its brightest to white.
+    #[arg(long, default_value_t = false)]
+    pub auto_contrast: bool,
+    /// Mirror the image left-to-right.
+    #[arg(long, default_value_t = false)]
+    pub flip_h: bool,
+    /// Mirror the image top-to-bottom.
+    #[arg(long, default_value_t = false)]
+    pub flip_v: bool,
+    /// Rotate the image clockwise by this many degrees, before resizing.
+    #[arg(long, value_enum)]
+    pub rotate: Option<Rotation>,
+    /// Commit-message template, with placeholders `{index}` (this commit's number that day),
+    /// `{total}` (that day's number of commits), `{date}`, `{iso8601}`, and `{pixel}`
+    /// [default: `#{index}/{pixel}`]
+    #[arg(long)]
+    pub message: Option<String>,
+    /// File of commit-message templates (like `--message`), one per line,
+    /// to cycle through commit by commit.
+    #[arg(long)]
+    pub message_file: Option<PathBuf>,
+    /// Time of day (HH:MM:SS, in UTC) to commit at, before `--jitter`.
+    #[arg(long, default_value = "12:00:00")]
+    pub commit_time: NaiveTime,
+    /// Spread each day's commits evenly across `--spread-window` around `--commit-time`
+    /// instead of making them all at once.
+    #[arg(long, default_value_t = false)]
+    pub spread_commits: bool,
+    /// Number of hours (up to 24) that `--spread-commits` spreads each day's commits across.
+    #[arg(long, default_value_t = 8.)]
+    pub spread_window: f32,
+    /// Draw each commit's time from a normal distribution around `--commit-time`
+    /// with this standard deviation (in minutes), like commits arriving throughout the day.
+    #[arg(long, conflicts_with = "spread_commits")]
+    pub gaussian_time: Option<f32>,
+    /// Move each commit up to this many seconds (uniformly at random) before or after `--commit-time`,
+    /// staying within the same day.
+    #[arg(long, default_value_t = 0)]
+    pub jitter: u32,
+    /// Seed for everything random (e.g. `--jitter`, `--noise`, and `--pattern random`): the same seed, image, and other arguments
+    /// (including dates, which otherwise depend on today) always make exactly the same history.
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// Never make more than this many commits on one day, whatever `--scale` says.
+    #[arg(long)]
+    pub max_commits: Option<u8>,
+    /// Make at least this many commits on each day whose pixel isn't pure black,
+    /// even where `--scale` would round it down (e.g. to 0 for a pixel of 10 at `--scale 5`);
+    /// `--min-commits 0` lets faint pixels disappear.
+    #[arg(long, default_value_t = 1)]
+    pub min_commits: u8,
+    /// Before committing, write the whole plan of commits as JSON to this file (`-` for stdout);
+    /// with `--dry-run`, this replaces the usual tab-separated output.
+    #[cfg(feature = "output-json")]
+    #[arg(long)]
+    pub output_json: Option<PathBuf>,
+    /// Before committing, write each planned commit as a CSV row
+    /// (`date,commit_index,total_for_day,message`) to this file (`-` for stdout).
+    #[arg(long)]
+    pub output_csv: Option<PathBuf>,
+    /// Before committing, draw the planned contribution graph as an SVG in this file.
+    #[arg(long)]
+    pub output_svg: Option<PathBuf>,
+    /// Before committing, write the plan as an iCalendar (`.ics`) file in this file,
+    /// with an all-day event for each day with any commits.
+    #[arg(long)]
+    pub output_ical: Option<PathBuf>,
+    /// Before committing, draw the planned contribution graph as an image (e.g. a PNG) in this file.
+    #[arg(long)]
+    pub output_preview: Option<PathBuf>,
+    /// Write the pattern (after all preprocessing, before repeating) as a grayscale image in this file,
+    /// one pixel per cell.
+    #[arg(long)]
+    pub output_grid_png: Option<PathBuf>,
+    /// Summarize the plan and ask before committing anything
+    /// (unless standard input isn't a terminal).
+    #[arg(long, default_value_t = false)]
+    pub confirm: bool,
+    /// Like `--confirm`, but fail if standard input isn't a terminal.
+    #[arg(long, default_value_t = false)]
+    pub require_confirm: bool,
+    /// Answer yes in advance to `--confirm` and `--require-confirm`.
+    #[arg(short, long, default_value_t = false)]
+    pub yes: bool,
+    /// Describe each commit (its OID, date, number within the day, parent, and message)
+    /// on standard error right after making it, even with `--quiet`.
+    #[arg(short, long, default_value_t = false)]
+    pub verbose: bool,
+    /// Summarize the plan (commits in total, active days, and commits per active day)
+    /// on standard error before drawing it, as `--verbose` also does.
+    #[arg(long, default_value_t = false)]
+    pub stats: bool,
+    /// Print how many days get each number of commits (e.g. to tune `--scale`, `--threshold`, or `--curve`)
+    /// on standard output before drawing.
+    #[arg(long, default_value_t = false)]
+    pub list_commit_counts: bool,
+    /// Print every day's brightness (after all preprocessing and repeating) as a table,
+    /// one row per day of the week and one column per week, then stop without committing.
+    #[arg(long, default_value_t = false)]
+    pub print_columns: bool,
+    /// Put a copy of this file in every commit (instead of committing an empty tree)
+    #[arg(long)]
+    pub content_file: Option<PathBuf>,
+    /// Put a `README.md` in every commit, filling in `{date}`, `{commit_index}`
+    /// (within the day, starting at 1), `{image_name}`, and `{name}` (yours)
+    #[arg(long)]
+    pub readme_template: Option<String>,
+    /// Instead of drawing, write a GitHub Actions workflow here
+    /// (usually `.github/workflows/contributron.yml`)
+    /// that draws with the rest of these arguments every Monday at 09:00 UTC
+    #[arg(long)]
+    #[serde(skip)]
+    pub generate_workflow: Option<PathBuf>,
+    /// Keep running, and draw again whenever `--image` changes,
+    /// recreating the repository (with `--force`), adding to it (with `--append` or `--update`),
+    /// or just planning (with `--dry-run`)
+    #[arg(long, default_value_t = false)]
+    #[serde(skip)]
+    pub watch: bool,
+    /// Make exactly the commits in a plan saved by `--output-json`, skipping the image entirely.
+    #[cfg(feature = "output-json")]
+    #[arg(long, conflicts_with_all = ["image", "text", "noise", "pattern", "qr", "per_day_file", "from_git_log"])]
+    pub from_plan: Option<PathBuf>,
+    /// After each day's commits, record (as JSON) that day and the commit the reference is on,
+    /// so that `--resume` can pick up from there if interrupted
+    #[cfg(feature = "output-json")]
+    #[arg(long)]
+    pub checkpoint: Option<PathBuf>,
+    /// Continue an interrupted run on the existing repository from the day after `--checkpoint`'s,
+    /// as long as its reference is still on the checkpoint's commit
+    #[cfg(feature = "output-json")]
+    #[arg(
+        long,
+        default_value_t = false,
+        requires = "checkpoint",
+        conflicts_with = "overwrite"
+    )]
+    pub resume: bool,
+    /// Resume even if the reference has moved since the checkpoint (e.g. after other Git operations).
+    #[cfg(feature = "output-json")]
+    #[arg(long, default_value_t = false, requires = "resume")]
+    pub force_resume: bool,
+}
+
+/// Clockwise rotation, in degrees.
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum, serde::Deserialize, serde::Serialize,
+)]
+pub enum Rotation {
+    /// A quarter turn clockwise.
+    #[value(name = "90")]
+    #[serde(rename = "90")]
+    Quarter,
+    /// A half turn.
+    #[value(name = "180")]
+    #[serde(rename = "180")]
+    Half,
+    /// A quarter turn counterclockwise.
+    #[value(name = "270")]
+    #[serde(rename = "270")]
+    ThreeQuarters,
+}
+
+/// Which way to mirror an image.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Axis {
+    /// Left-to-right (reverse the order of the columns).
+    Horizontal,
+    /// Top-to-bottom (reverse each column).
+    Vertical,
+}
+
+/// Where to put an image narrower than the contribution graph.
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum, serde::Deserialize, serde::Serialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Align {
+    /// Against the oldest week (the same as `--offset 0`).
+    Left,
+    /// In the middle.
+    Center,
+    /// Against the most recent week.
+    Right,
+}
+
+/// How to convert a color pixel to a single brightness.
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum, serde::Deserialize, serde::Serialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum GrayscaleMethod {
+    /// Weighted by perceived brightness: 0.2126 R + 0.7152 G + 0.0722 B.
+    Luminosity,
+    /// (R + G + B) / 3.
+    Average,
+    /// (max(R, G, B) + min(R, G, B)) / 2.
+    Lightness,
+}
+
+/// How to resample an image when resizing it.
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum, serde::Deserialize, serde::Serialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum ResizeFilter {
+    /// Nearest-neighbor sampling (crisp, blocky).
+    Nearest,
+    /// Linear interpolation (smooth).
+    Bilinear,
+    /// Lanczos with a window of 3 (sharp, best for photographs).
+    Lanczos3,
+}
+
+impl From<ResizeFilter> for FilterType {
+    #[inline]
+    fn from(filter: ResizeFilter) -> Self {
+        match filter {
+            ResizeFilter::Nearest => Self::Nearest,
+            ResizeFilter::Bilinear => Self::Triangle,
+            ResizeFilter::Lanczos3 => Self::Lanczos3,
+        }
+    }
+}
+
+/// Where and as whom to commit.
+pub struct GitInfo<'reference, 'name, 'email> {
+    /// Repository to commit to.
+    pub repo: git2::Repository,
+    /// Git reference (usually a branch name) to move forward with each commit.
+    pub reference: &'reference str,
+    /// Name of the committer.
+    pub name: &'name str,
+    /// Email of the committer.
+    pub email: &'email str,
+    /// Name of the author (often the same as `name`).
+    pub author_name: &'name str,
+    /// Email of the author (often the same as `email`).
+    pub author_email: &'email str,
+    /// Print commits instead of making them.
+    pub dry_run: bool,
+    /// UTC offset, in minutes, recorded in each commit's timestamp.
+    pub offset_minutes: i32,
+    /// Sign each commit, if set.
+    pub gpg: Option<GpgSigner>,
+    /// Print nothing (not even a dry run's commits), except to standard error.
+    pub quiet: bool,
+    /// Commit-message templates (see `format_message`), cycled through commit by commit.
+    pub messages: Vec<String>,
+    /// Number of commits made so far, to choose the next message.
+    pub commits_made: Cell<usize>,
+    /// Time of day (before `jitter_secs`) of every commit.
+    pub commit_time: NaiveTime,
+    /// Spread each day's commits evenly across this many hours around `commit_time`, if set.
+    pub spread_hours: Option<f32>,
+    /// Otherwise, draw each commit's time from a normal distribution around `commit_time`
+    /// with this standard deviation (in minutes), if set.
+    pub gaussian_minutes: Option<f32>,
+    /// Move each commit up to this many seconds (randomly) before or after `commit_time`.
+    pub jitter_secs: u32,
+    /// Random number generator for `jitter_secs`, seeded by `--seed`
+    /// (or, if `None`, use the thread's).
+    pub rng: Option<RefCell<SmallRng>>,
+    /// Make at least this many commits on each day whose pixel isn't black.
+    pub min_commits: u8,
+    /// Describe each commit (to standard error) right after making it.
+    pub verbose: bool,
+    /// Never make more than this many commits on one day.
+    pub max_commits: Option<u8>,
+    /// Draw each day's number of commits from a Poisson distribution (see `poisson_counts`).
+    pub poisson: bool,
+    /// Put a copy of this file (in the root of the repository) in every commit, if set.
+    pub content_file: Option<PathBuf>,
+    /// Put a `README.md` rendered from this template (see `render_readme`) in every commit, if set.
+    pub readme_template: Option<String>,
+    /// File name of the image drawn, for `readme_template`.
+    pub image_name: String,
+    /// Record progress here after each day (see `Checkpoint`), if set.
+    pub checkpoint: Option<PathBuf>,
+}
+
+/// Draw `pattern` over and over (separated by a blank column) until `dates` are covered.
+/// # Errors
+/// If `dates` is backwards or any commit fails.
+#[inline]
+pub fn draw_repeating_pattern(
+    git: &GitInfo,
+    pattern: &dyn PatternSource,
+    dates: RangeInclusive<NaiveDate>,
+    scale: u8,
+    dither: Option<DitherMode>,
+    #[cfg(feature = "progress")] progress: Option<&indicatif::ProgressBar>,
+) -> Result<(), ContributronError> {
+    let plan = plan(&pattern.columns(), dates, scale, dither, git)?;
+    draw_plan(
+        git,
+        &plan,
+        #[cfg(feature = "progress")]
+        progress,
+    )
+}
+
+/// Make every commit in `plan` (or print them, in a dry run).
+/// # Errors
+/// If any commit fails.
+#[inline]
+pub fn draw_plan(
+    git: &GitInfo,
+    plan: &CommitPlan,
+    #[cfg(feature = "progress")] progress: Option<&indicatif::ProgressBar>,
+) -> Result<(), ContributronError> {
+    #[cfg(not(feature = "progress"))]
+    let start_date = plan.entries.first().map(|entry| entry.date);
+    let tree = commit_tree(git)?;
+    for entry in &plan.entries {
+        let () = draw_entry(git, &tree, entry)?;
+        #[cfg(feature = "output-json")]
+        if let Some(ref path) = git.checkpoint
+            && !git.dry_run
+            && let Some(head) = head_commit(git)
+        {
+            let () = write_checkpoint(
+                path,
+                &Checkpoint {
+                    last_completed_date: entry.date,
+                    head_oid: head.to_string(),
+                },
+            )?;
+        }
+
+        #[cfg(feature = "progress")]
+        if let Some(progress) = progress {
+            let () = progress.set_message(entry.date.to_string());
+            let () = progress.inc(1);
+        }
+        #[cfg(not(feature = "progress"))]
+        if !git.quiet
+            && !git.dry_run
+            && let Some(start_date) = start_date
+        {
+            println!(
+                "{:3}% ({})",
+                entry.date.signed_duration_since(start_date).num_days() * 100
+                    / (plan.entries.len() as i64).max(1),
+                entry.date,
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Like `draw_plan`, but splitting `plan` into `jobs` consecutive parts drawn at once,
+/// each in its own thread (with its own handle on the repository) on a branch `part-{i}`,
+/// whose commits are then copied, in order, onto `git.reference`.
+/// Needs no GPG signing and no `README.md` (see `validate`),
+/// since each part's commits only get their final parents when copied.
+/// # Errors
+/// If any part fails, or if its commits can't be copied.
+#[inline]
+pub fn draw_plan_in_parts(
+    git: &GitInfo,
+    plan: &CommitPlan,
+    jobs: usize,
+    #[cfg(feature = "progress")] progress: Option<&indicatif::ProgressBar>,
+) -> Result<(), ContributronError> {
+    if jobs <= 1 || git.dry_run || plan.entries.is_empty() {
+        return draw_plan(
+            git,
+            plan,
+            #[cfg(feature = "progress")]
+            progress,
+        );
+    }
+    // Write the tree once, so that the parts don't all stage `--content-file` in the same index:
+    let tree = commit_tree(git)?.id();
+    let names: Vec<(String, String)> = (0..jobs)
+        .map(|i| {
+            (
+                format!("refs/contributron/part-{i}"),
+                format!("refs/heads/part-{i}"),
+            )
+        })
+        .collect();
+    let mut parts = Vec::with_capacity(jobs);
+    for (i, (entries, (symbolic, branch))) in plan
+        .entries
+        .chunks(plan.entries.len().div_ceil(jobs))
+        .zip(&names)
+        .enumerate()
+    {
+        let repo = match git2::Repository::open(git.repo.path()) {
+            Ok(ok) => ok,
+            Err(source) => {
+                return Err(ContributronError::RepoOpen {
+                    path: git.repo.path().to_path_buf(),
+                    source,
+                });
+            }
+        };
+        // A part starts with no commits, so point a symbolic reference at its branch until it has some:
+        match repo.reference_symbolic(symbolic, branch, true, &format!("part {i}")) {
+            Ok(_) => {}
+            Err(source) => {
+                return Err(ContributronError::Git {
+                    context: format!("Couldn't create `{symbolic}`"),
+                    source,
+                });
+            }
+        }
+        let part = GitInfo {
+            repo,
+            reference: symbolic,
+            name: git.name,
+            email: git.email,
+            author_name: git.author_name,
+            author_email: git.author_email,
+            dry_run: false,
+            offset_minutes: git.offset_minutes,
+            gpg: None,
+            quiet: git.quiet,
+            messages: vec![],
+            commits_made: Cell::new(0),
+            commit_time: git.commit_time,
+            spread_hours: git.spread_hours,
+            gaussian_minutes: git.gaussian_minutes,
+            jitter_secs: git.jitter_secs,
+            rng: git
+                .rng
+                .as_ref()
+                .map(|rng| RefCell::new(SmallRng::seed_from_u64(rng.borrow_mut().next_u64()))),
+            min_commits: git.min_commits,
+            max_commits: git.max_commits,
+            poisson: git.poisson,
+            verbose: false,
+            content_file: None,
+            readme_template: None,
+            image_name: String::new(),
+            checkpoint: None,
+        };
+        let () = parts.push((part, entries));
+    }
+
+    let pool = match rayon::ThreadPoolBuilder::new().num_threads(jobs).build() {
+        Ok(ok) => ok,
+        Err(e) => {
+            return Err(ContributronError::InvalidArgument(format!(
+                "Couldn't start {jobs} threads: {e}"
+            )));
+        }
+    };
+    let () = pool.install(|| {
+        parts.into_par_iter().try_for_each(|(part, entries)| {
+            let tree = match part.repo.find_tree(tree) {
+                Ok(ok) => ok,
+                Err(source) => {
+                    return Err(ContributronError::Git {
+                        context: "Couldn't find the repo's tree".to_owned(),
+                        source,
+                    });
+                }
+            };
+            for entry in entries {
+                let () = draw_entry(&part, &tree, entry)?;
+                #[cfg(feature = "progress")]
+                if let Some(progress) = progress {
+                    let () = progress.set_message(entry.date.to_string());
+                    let () = progress.inc(1);
+                }
+            }
+            Ok(())
+        })
+    })?;
+
+    let mut parent = match git.repo.find_reference(git.reference) {
+        Ok(reference) => reference.peel_to_commit().ok(),
+        Err(source) => {
+            return Err(ContributronError::ReferenceNotFound {
+                reference: git.reference.to_owned(),
+                source,
+            });
+        }
+    };
+    for (symbolic, branch) in &names {
+        // A part of only black pixels has no commits (so no branch):
+        if let Ok(head) = git.repo.refname_to_id(branch) {
+            let () = copy_part(git, head, &mut parent)?;
+        }
+        for name in [symbolic, branch] {
+            if let Ok(mut reference) = git.repo.find_reference(name) {
+                match reference.delete() {
+                    Ok(()) => {}
+                    Err(source) => {
+                        return Err(ContributronError::Git {
+                            context: format!("Couldn't delete `{name}`"),
+                            source,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Copy every commit up to `head` (oldest first, keeping its signatures, message, and tree)
+/// onto `parent`, moving `git.reference` (and `parent`) to each copy.
+/// # Errors
+/// If Git fails to walk the commits or to copy one.
+#[inline]
+fn copy_part<'repo>(
+    git: &'repo GitInfo,
+    head: git2::Oid,
+    parent: &mut Option<git2::Commit<'repo>>,
+) -> Result<(), ContributronError> {
+    let walk_failed = |source| ContributronError::Git {
+        context: format!("Couldn't walk the commits up to {head}"),
+        source,
+    };
+    let mut revwalk = match git.repo.revwalk() {
+        Ok(ok) => ok,
+        Err(source) => return Err(walk_failed(source)),
+    };
+    match revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE) {
+        Ok(()) => {}
+        Err(source) => return Err(walk_failed(source)),
+    }
+    match revwalk.push(head) {
+        Ok(()) => {}
+        Err(source) => return Err(walk_failed(source)),
+    }
+    for oid in revwalk {
+        let commit = match oid.and_then(|oid| git.repo.find_commit(oid)) {
+            Ok(ok) => ok,
+            Err(source) => return Err(walk_failed(source)),
+        };
+        let message = commit.message().unwrap_or_default();
+        let tree = match commit.tree() {
+            Ok(ok) => ok,
+            Err(source) => return Err(walk_failed(source)),
+        };
+        let parents: &[&_] = if let Some(ref parent) = *parent {
+            &[parent]
+        } else {
+            &[]
+        };
+        let oid = match git.repo.commit(
+            Some(git.reference),
+            &commit.author(),
+            &commit.committer(),
+            message,
+            &tree,
+            parents,
+        ) {
+            Ok(ok) => ok,
+            Err(source) => {
+                return Err(ContributronError::CommitFailed {
+                    reference: git.reference.to_owned(),
+                    message: message.to_owned(),
+                    source,
+                });
+            }
+        };
+        if git.verbose {
+            eprintln!("[commit] {oid} (copied from {}): {message}", commit.id());
+        }
+        *parent = Some(match git.repo.find_commit(oid) {
+            Ok(ok) => ok,
+            Err(source) => {
+                return Err(ContributronError::Git {
+                    context: format!("Couldn't find the commit we just made (OID {oid})"),
+                    source,
+                });
+            }
+        });
+    }
+    Ok(())
+}
+
+/// Which of `git.messages` the `n`th commit (counting from 0) uses.
+#[inline]
+fn message_template<'git>(git: &'git GitInfo, n: usize) -> &'git str {
+    match git.messages.get(n % git.messages.len().max(1)) {
+        Some(some) => some,
+        None => DEFAULT_MESSAGE,
+    }
+}
+
+/// Write everything in `mempack` (see `--in-memory`) to `odb` as one pack, then forget it.
+/// # Errors
+/// If Git can't build or write the pack.
+#[inline]
+fn write_pack(
+    repo: &git2::Repository,
+    odb: &git2::Odb,
+    mempack: &git2::Mempack,
+) -> Result<(), ContributronError> {
+    let pack_failed = |source| ContributronError::Git {
+        context: "Couldn't write the in-memory Git objects as a pack".to_owned(),
+        source,
+    };
+    let mut pack = git2::Buf::new();
+    match mempack.dump(repo, &mut pack) {
+        Ok(()) => {}
+        Err(source) => return Err(pack_failed(source)),
+    }
+    let mut writer = match odb.packwriter() {
+        Ok(ok) => ok,
+        Err(source) => return Err(pack_failed(source)),
+    };
+    match writer.write_all(&pack) {
+        Ok(()) => {}
+        Err(source) => {
+            return Err(ContributronError::Io {
+                context: "Couldn't write the in-memory Git objects as a pack".to_owned(),
+                source,
+            });
+        }
+    }
+    match writer.commit() {
+        Ok(_) => {}
+        Err(source) => return Err(pack_failed(source)),
+    }
+    match mempack.reset() {
+        Ok(()) => Ok(()),
+        Err(source) => Err(pack_failed(source)),
+    }
+}
+
+/// Draw `plan` (see `draw_plan_in_parts`), then (unless it's a dry run)
+/// save how to undo it (see `undo`) and, given a prefix and whether to force it, tag it (see `tag_boundaries`).
+/// # Errors
+/// If any of those fails.
+#[inline]
+fn draw_and_record(
+    git: &GitInfo,
+    plan: &CommitPlan,
+    jobs: usize,
+    before: Option<git2::Oid>,
+    tag: Option<(&str, bool)>,
+    #[cfg(feature = "progress")] progress: &indicatif::ProgressBar,
+) -> Result<(), ContributronError> {
+    let () = draw_plan_in_parts(
+        git,
+        plan,
+        jobs,
+        #[cfg(feature = "progress")]
+        Some(progress),
+    )?;
+    #[cfg(feature = "progress")]
+    let () = progress.finish();
+    if git.dry_run {
+        return Ok(());
+    }
+    let () = save_undo(git, before)?;
+    if let Some((prefix, force)) = tag {
+        let () = tag_boundaries(git, before, prefix, force)?;
+    }
+    Ok(())
+}
+
+/// Number of days in `dates`, inclusive.
+#[inline]
+fn count_days(dates: &RangeInclusive<NaiveDate>) -> Result<usize, ContributronError> {
+    let (start_date, end_date) = (*dates.start(), *dates.end());
+    let n_days = end_date.signed_duration_since(start_date).num_days() + 1;
+    match usize::try_from(n_days) {
+        Ok(ok) => Ok(ok),
+        Err(e) => Err(ContributronError::InvalidDateArithmetic(format!(
+            "{n_days} days between {start_date} and {end_date}: {e}"
+        ))),
+    }
+}
+
+/// One pixel per day for `n_days` days, one column per week (so only the first seven rows count),
+/// repeating the columns of `grid` (each copy followed by a blank column) as needed.
+#[inline]
+fn repeat_pattern(grid: &ContributionGrid, n_days: usize) -> Vec<u8> {
+    let mut copy = ContributionGrid::from_columns(grid.cells.clone(), 7);
+    let () = copy.cells.push(vec![0; 7]);
+    copy.cols += 1;
+    ContributionGrid::tile(&copy, n_days.div_ceil(7))
+        .cells
+        .into_iter()
+        .flatten()
+        .take(n_days)
+        .collect()
+}
+
+/// One column per seven of `days`, with any missing from the last filled in with zeros.
+#[inline]
+fn weeks(days: &[u8]) -> ContributionGrid {
+    ContributionGrid::from_columns(days.chunks(7).map(<[u8]>::to_vec).collect(), 7)
+}
+
+/// Seconds after midnight of each of a day's `n_commits` commits, in order:
+/// `commit_time` (or, with `spread_hours`, spread around it) plus `jitter_seconds`, within the day.
+#[inline]
+fn commit_seconds(git: &GitInfo, n_commits: u8) -> Vec<i64> {
+    let times = match (git.spread_hours, git.gaussian_minutes) {
+        (Some(window_hours), _) => spread_times(n_commits, git.commit_time, window_hours),
+        (None, Some(sigma_minutes)) => match git.rng {
+            Some(ref rng) => gaussian_times(
+                n_commits,
+                git.commit_time,
+                sigma_minutes,
+                &mut rng.borrow_mut(),
+            ),
+            None => gaussian_times(
+                n_commits,
+                git.commit_time,
+                sigma_minutes,
+                &mut SmallRng::from_entropy(),
+            ),
+        },
+        (None, None) => vec![git.commit_time; usize::from(n_commits)],
+    };
+    let mut seconds: Vec<i64> = times
+        .into_iter()
+        .zip(jitter_seconds(git, n_commits))
+        .map(|(time, jitter)| {
+            (i64::from(time.num_seconds_from_midnight()) + jitter).clamp(0, LAST_SECOND.into())
+        })
+        .collect();
+    let () = seconds.sort_unstable();
+    seconds
+}
+
+/// Seconds after midnight of the last second of a day.
+const LAST_SECOND: u32 = 24 * 60 * 60 - 1;
+
+/// `count` times evenly spaced from `window_hours / 2` hours before `base` to as long after,
+/// but no earlier or later than the same day (with a single time just at `base`).
+#[inline]
+#[must_use]
+pub fn spread_times(count: u8, base: NaiveTime, window_hours: f32) -> Vec<NaiveTime> {
+    let base = base.num_seconds_from_midnight() as f32;
+    let window = window_hours * 60. * 60.;
+    (0..count)
+        .map(|i| {
+            let seconds = if count > 1 {
+                base - window / 2. + window * f32::from(i) / f32::from(count - 1)
+            } else {
+                base
+            };
+            let seconds = seconds.clamp(0., LAST_SECOND as f32).round() as u32;
+            NaiveTime::from_num_seconds_from_midnight_opt(seconds, 0).unwrap_or(NaiveTime::MIN)
+        })
+        .collect()
+}
+
+/// `count` times drawn from a normal distribution around `base`
+/// with a standard deviation of `sigma_minutes`, within the same day and in ascending order.
+#[inline]
+pub fn gaussian_times(
+    count: u8,
+    base: NaiveTime,
+    sigma_minutes: f32,
+    rng: &mut SmallRng,
+) -> Vec<NaiveTime> {
+    let base = base.num_seconds_from_midnight() as f32;
+    let mut seconds: Vec<u32> = match Normal::new(base, sigma_minutes * 60.) {
+        Ok(normal) => (0..count)
+            .map(|_| normal.sample(rng).clamp(0., LAST_SECOND as f32).round() as u32)
+            .collect(),
+        // An invalid (e.g. negative) sigma can't spread anything:
+        Err(_) => vec![base as u32; usize::from(count)],
+    };
+    let () = seconds.sort_unstable();
+    seconds
+        .into_iter()
+        .map(|seconds| {
+            NaiveTime::from_num_seconds_from_midnight_opt(seconds, 0).unwrap_or(NaiveTime::MIN)
+        })
+        .collect()
+}
+
+/// Random offsets from `commit_time` (in seconds, in ascending order, and within the same day)
+/// for each of `n_commits` commits, or all zero without `--jitter`.
+#[inline]
+fn jitter_seconds(git: &GitInfo, n_commits: u8) -> Vec<i64> {
+    if git.jitter_secs == 0 {
+        return vec![0; usize::from(n_commits)];
+    }
+    // Stay within the same day:
+    let before = i64::from(git.commit_time.num_seconds_from_midnight());
+    let after = 24 * 60 * 60 - 1 - before;
+    let range = -i64::from(git.jitter_secs).min(before)..=i64::from(git.jitter_secs).min(after);
+    let mut offsets: Vec<i64> = match git.rng {
+        Some(ref rng) => {
+            let mut rng = rng.borrow_mut();
+            (0..n_commits)
+                .map(|_| rng.gen_range(range.clone()))
+                .collect()
+        }
+        None => {
+            let mut rng = rand::thread_rng();
+            (0..n_commits)
+                .map(|_| rng.gen_range(range.clone()))
+                .collect()
+        }
+    };
+    let () = offsets.sort_unstable();
+    offsets
+}
+
+/// Apply `--min-commits` (if the day's pixel isn't black) and then `--max-commits`
+/// to a day's number of commits.
+#[inline]
+#[must_use]
+pub fn limit_commits(pixel: u8, n_commits: u8, min_commits: u8, max_commits: Option<u8>) -> u8 {
+    let n_commits = if pixel > 0 {
+        n_commits.max(min_commits)
+    } else {
+        n_commits
+    };
+    match max_commits {
+        Some(max_commits) => n_commits.min(max_commits),
+        None => n_commits,
+    }
+}
+
+/// File (in a repository's `.git` directory) recording how to undo the last run (see `undo`).
+pub const UNDO_FILE: &str = ".contributron-undo";
+
+/// Git's well-known empty tree, which (without `--content-file`) every commit shares.
+pub const EMPTY_TREE: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
+/// Write (once, before any commits) the tree that every commit shares:
+/// just `git.content_file`, if set, or otherwise empty.
+/// # Errors
+/// If the content file can't be copied into the repository's working directory
+/// (e.g. there isn't one), or Git fails to write or find the tree.
+#[inline]
+pub fn commit_tree<'repo>(git: &'repo GitInfo) -> Result<git2::Tree<'repo>, ContributronError> {
+    let tree_id = if git.dry_run {
+        // A dry run's in-memory repository can't be written to, but Git already knows this tree:
+        match git2::Oid::from_str(EMPTY_TREE) {
+            Ok(ok) => ok,
+            Err(source) => {
+                return Err(ContributronError::Git {
+                    context: format!("Internal error: invalid OID `{EMPTY_TREE}`"),
+                    source,
+                });
+            }
+        }
+    } else {
+        let mut index = match git.repo.index() {
+            Ok(ok) => ok,
+            Err(source) => {
+                return Err(ContributronError::Git {
+                    context: "Couldn't fetch the repo's index".to_owned(),
+                    source,
+                });
+            }
+        };
+        if let Some(ref content_file) = git.content_file {
+            let () = stage_copy(&git.repo, &mut index, content_file)?;
+        }
+        match index.write_tree() {
+            Ok(ok) => ok,
+            Err(source) => {
+                return Err(ContributronError::Git {
+                    context: "Couldn't write the repo's tree".to_owned(),
+                    source,
+                });
+            }
+        }
+    };
+    match git.repo.find_tree(tree_id) {
+        Ok(ok) => Ok(ok),
+        Err(source) => Err(ContributronError::Git {
+            context: "Couldn't find the repo's tree".to_owned(),
+            source,
+        }),
+    }
+}
+
+/// Copy `file` into the root of `repo`'s working directory and stage it.
+/// # Errors
+/// If `repo` is bare, `file` can't be copied, or Git fails to stage it.
+#[inline]
+fn stage_copy(
+    repo: &git2::Repository,
+    index: &mut git2::Index,
+    file: &Path,
+) -> Result<(), ContributronError> {
+    let Some(workdir) = repo.workdir() else {
+        return Err(ContributronError::WorkdirNotAvailable(
+            repo.path().to_path_buf(),
+        ));
+    };
+    let Some(name) = file.file_name() else {
+        return Err(ContributronError::InvalidArgument(format!(
+            "`{}` isn't a file",
+            file.to_string_lossy(),
+        )));
+    };
+    match fs::copy(file, workdir.join(name)) {
+        Ok(_) => {}
+        Err(source) => {
+            return Err(ContributronError::Io {
+                context: format!(
+                    "Couldn't copy `{}` into `{}`",
+                    file.to_string_lossy(),
+                    workdir.to_string_lossy(),
+                ),
+                source,
+            });
+        }
+    }
+    match index.add_path(Path::new(name)) {
+        Ok(()) => {}
+        Err(source) => {
+            return Err(ContributronError::Git {
+                context: format!("Couldn't stage `{}`", name.to_string_lossy()),
+                source,
+            });
+        }
+    }
+    // Keep `git status` clean afterward:
+    match index.write() {
+        Ok(()) => Ok(()),
+        Err(source) => Err(ContributronError::Git {
+            context: "Couldn't write the repo's index".to_owned(),
+            source,
+        }),
+    }
+}
+
+/// Write `readme` to `README.md` in `git.repo`'s working directory, stage it,
+/// and write a tree of everything staged.
+/// # Errors
+/// If the repository is bare, or writing or staging anything fails.
+#[inline]
+fn readme_tree<'repo>(
+    git: &'repo GitInfo,
+    index: &mut git2::Index,
+    readme: &str,
+) -> Result<git2::Tree<'repo>, ContributronError> {
+    let Some(workdir) = git.repo.workdir() else {
+        return Err(ContributronError::WorkdirNotAvailable(
+            git.repo.path().to_path_buf(),
+        ));
+    };
+    let path = workdir.join("README.md");
+    match fs::write(&path, readme) {
+        Ok(()) => {}
+        Err(source) => {
+            return Err(ContributronError::Io {
+                context: format!("Couldn't write `{}`", path.to_string_lossy()),
+                source,
+            });
+        }
+    }
+    match index.add_path(Path::new("README.md")) {
+        Ok(()) => {}
+        Err(source) => {
+            return Err(ContributronError::Git {
+                context: "Couldn't stage `README.md`".to_owned(),
+                source,
+            });
+        }
+    }
+    // Keep `git status` clean afterward:
+    match index.write() {
+        Ok(()) => {}
+        Err(source) => {
+            return Err(ContributronError::Git {
+                context: "Couldn't write the repo's index".to_owned(),
+                source,
+            });
+        }
+    }
+    let tree_id = match index.write_tree() {
+        Ok(ok) => ok,
+        Err(source) => {
+            return Err(ContributronError::Git {
+                context: "Couldn't write the repo's tree".to_owned(),
+                source,
+            });
+        }
+    };
+    match git.repo.find_tree(tree_id) {
+        Ok(ok) => Ok(ok),
+        Err(source) => Err(ContributronError::Git {
+            context: "Couldn't find the repo's tree".to_owned(),
+            source,
+        }),
+    }
+}
+
+/// Make `n_commits` commits on `date` (or print them, in a dry run), all with `tree` (see `commit_tree`).
+/// # Errors
+/// If the reference doesn't exist or Git fails to commit.
+#[inline]
+pub fn draw_pixel(
+    git: &GitInfo,
+    tree: &git2::Tree,
+    pixel: u8,
+    n_commits: u8,
+    date: NaiveDate,
+) -> Result<(), ContributronError> {
+    let n_commits = limit_commits(pixel, n_commits, git.min_commits, git.max_commits);
+    let entry = plan_day(git, git.commits_made.get(), pixel, n_commits, date)?;
+    draw_entry(git, tree, &entry)
+}
+
+/// Plan `n_commits` commits on `date`, the first being overall commit number `first_commit`
+/// (counting from 0, to choose its message).
+#[inline]
+fn plan_day(
+    git: &GitInfo,
+    first_commit: usize,
+    pixel: u8,
+    n_commits: u8,
+    date: NaiveDate,
+) -> Result<CommitEntry, ContributronError> {
+    let times = commit_times(git, n_commits, date)?;
+    let mut messages = Vec::with_capacity(usize::from(n_commits));
+    for (i, &time) in (0..n_commits).zip(&times) {
+        let template = message_template(git, first_commit + usize::from(i));
+        let () = messages.push(format_message(
+            template,
+            i + 1,
+            n_commits,
+            pixel,
+            date,
+            time,
+        )?);
+    }
+    Ok(CommitEntry {
+        date,
+        pixel,
+        commit_count: n_commits,
+        messages,
+        times,
+    })
+}
+
+/// The author time of each of `n_commits` commits on `date`, in order (see `commit_seconds`),
+/// in `git.offset_minutes`' time zone.
+/// # Errors
+/// If that time zone is out of range or a time is out of chrono's range.
+#[inline]
+fn commit_times(
+    git: &GitInfo,
+    n_commits: u8,
+    date: NaiveDate,
+) -> Result<Vec<DateTime<FixedOffset>>, ContributronError> {
+    let Some(offset) = FixedOffset::east_opt(git.offset_minutes * 60) else {
+        return Err(ContributronError::InvalidArgument(format!(
+            "a time zone {} minutes from UTC is out of range",
+            git.offset_minutes
+        )));
+    };
+    let midnight = date.and_time(NaiveTime::MIN).and_utc();
+    commit_seconds(git, n_commits)
+        .into_iter()
+        .map(|seconds| {
+            match TimeDelta::try_seconds(seconds)
+                .and_then(|delta| midnight.checked_add_signed(delta))
+            {
+                Some(some) => Ok(some.with_timezone(&offset)),
+                None => Err(ContributronError::InvalidDateArithmetic(format!(
+                    "couldn't add {seconds} seconds to {midnight}"
+                ))),
+            }
+        })
+        .collect()
+}
+
+/// Make one day's planned commits (or print them, in a dry run), all with `tree`
+/// (plus, with `git.readme_template`, a `README.md` of their own).
+/// # Errors
+/// If the reference doesn't exist or Git fails to commit.
+#[inline]
+pub fn draw_entry(
+    git: &GitInfo,
+    tree: &git2::Tree,
+    entry: &CommitEntry,
+) -> Result<(), ContributronError> {
+    let CommitEntry {
+        date,
+        pixel,
+        commit_count: n_commits,
+        ref messages,
+        ref times,
+    } = *entry;
+    if git.dry_run {
+        if !git.quiet {
+            for i in 0..n_commits {
+                println!("{date}\t{pixel}\t{}\t{n_commits}", i + 1);
+            }
+        }
+        return Ok(());
+    }
+
+    // Plans saved before commit times were recorded don't have them.
+    let planned;
+    let times = if times.len() == usize::from(n_commits) {
+        times
+    } else {
+        planned = commit_times(git, n_commits, date)?;
+        &planned
+    };
+
+    let mut parent = {
+        let reference = match git.repo.find_reference(git.reference) {
+            Ok(ok) => ok,
+            Err(source) => {
+                return Err(ContributronError::ReferenceNotFound {
+                    reference: git.reference.to_owned(),
+                    source,
+                });
+            }
+        };
+        reference.peel_to_commit().ok()
+    };
+    let mut index = match git.readme_template {
+        None => None,
+        Some(_) => match git.repo.index() {
+            Ok(ok) => Some(ok),
+            Err(source) => {
+                return Err(ContributronError::Git {
+                    context: "Couldn't fetch the repo's index".to_owned(),
+                    source,
+                });
+            }
+        },
+    };
+
+    for ((i, message), time) in (1_u16..).zip(messages).zip(times) {
+        let own_tree = match (&git.readme_template, &mut index) {
+            (Some(template), Some(index)) => {
+                let readme = render_readme(
+                    template,
+                    date,
+                    u8::try_from(i).unwrap_or(u8::MAX),
+                    &git.image_name,
+                    git.name,
+                );
+                Some(readme_tree(git, index, &readme)?)
+            }
+            _ => None,
+        };
+        let tree = own_tree.as_ref().unwrap_or(tree);
+        let time = git2::Time::new(time.timestamp(), git.offset_minutes);
+        let author_sig = signature(git.author_name, git.author_email, time)?;
+        let committer_sig = signature(git.name, git.email, time)?;
+        let () = git.commits_made.set(git.commits_made.get() + 1);
+        let parents: &[&_] = if let Some(ref parent) = parent {
+            &[parent]
+        } else {
+            &[]
+        };
+        let oid = if let Some(ref gpg) = git.gpg {
+            commit_signed(
+                git,
+                gpg,
+                &author_sig,
+                &committer_sig,
+                message,
+                tree,
+                parents,
+            )?
+        } else {
+            match git.repo.commit(
+                Some(git.reference),
+                &author_sig,
+                &committer_sig,
+                message,
+                tree,
+                parents,
+            ) {
+                Ok(ok) => ok,
+                Err(source) => {
+                    return Err(ContributronError::CommitFailed {
+                        reference: git.reference.to_owned(),
+                        message: message.clone(),
+                        source,
+                    });
+                }
+            }
+        };
+        if git.verbose {
+            let parent = match parent {
+                Some(ref parent) => parent.id().to_string(),
+                None => "(none)".to_owned(),
+            };
+            eprintln!("[commit] {oid} on {date} (#{i} of {n_commits}; parent {parent}): {message}");
+        }
+        parent = Some(match git.repo.find_commit(oid) {
+            Ok(ok) => ok,
+            Err(source) => {
+                return Err(ContributronError::Git {
+                    context: format!("Couldn't find the commit we just made (OID {oid})"),
+                    source,
+                });
+            }
+        });
+    }
+
+    Ok(())
+}
+
+/// A Git signature, or an error saying which part was invalid.
+#[inline]
+fn signature(
+    name: &str,
+    email: &str,
+    time: git2::Time,
+) -> Result<git2::Signature<'static>, ContributronError> {
+    match git2::Signature::new(name, email, &time) {
+        Ok(ok) => Ok(ok),
+        Err(source) => Err(ContributronError::Git {
+            context: format!(
+                "Couldn't create a Git signature from name `{name}`, email `{email}`, and time {time:?}"
+            ),
+            source,
+        }),
+    }
+}
+
+/// Like `git2::Repository::commit`, but signed with GPG.
+#[inline]
+fn commit_signed(
+    git: &GitInfo,
+    gpg: &GpgSigner,
+    author_sig: &git2::Signature,
+    committer_sig: &git2::Signature,
+    message: &str,
+    tree: &git2::Tree,
+    parents: &[&git2::Commit],
+) -> Result<git2::Oid, ContributronError> {
+    let commit_failed = |source| ContributronError::CommitFailed {
+        reference: git.reference.to_owned(),
+        message: message.to_owned(),
+        source,
+    };
+
+    let buffer =
+        match git
+            .repo
+            .commit_create_buffer(author_sig, committer_sig, message, tree, parents)
+        {
+            Ok(ok) => ok,
+            Err(source) => return Err(commit_failed(source)),
+        };
+    let Some(content) = buffer.as_str() else {
+        return Err(ContributronError::GpgSigningFailed(format!(
+            "Commit with message `{message}` isn't valid UTF-8"
+        )));
+    };
+    let signature = gpg.sign(content.as_bytes())?;
+    let oid = match git.repo.commit_signed(content, &signature, Some("gpgsig")) {
+        Ok(ok) => ok,
+        Err(source) => return Err(commit_failed(source)),
+    };
+
+    // Unlike `commit`, `commit_signed` doesn't move any reference,
+    // so move it (or, if symbolic like `HEAD`, whatever it points to) ourselves:
+    let name = match git.repo.find_reference(git.reference) {
+        Ok(reference) => match reference.symbolic_target() {
+            Some(target) => target.to_owned(),
+            None => git.reference.to_owned(),
+        },
+        Err(source) => {
+            return Err(ContributronError::ReferenceNotFound {
+                reference: git.reference.to_owned(),
+                source,
+            });
+        }
+    };
+    match git
+        .repo
+        .reference(&name, oid, true, &format!("commit: {message}"))
+    {
+        Ok(_) => Ok(oid),
+        Err(source) => Err(commit_failed(source)),
+    }
+}
+
+/// The weeks of the graph for calendar year `year`: from the start of the week of
+/// January 1 through the end of the week of December 31
+/// (on GitHub, from the Sunday on or before through the Saturday on or after).
+/// # Errors
+/// If `year` is too far in the past or future to represent.
+#[inline]
+pub fn year_window(
+    year: i32,
+    week_start: WeekStart,
+) -> Result<RangeInclusive<NaiveDate>, ContributronError> {
+    let (Some(january_1), Some(december_31)) = (
+        NaiveDate::from_ymd_opt(year, 1, 1),
+        NaiveDate::from_ymd_opt(year, 12, 31),
+    ) else {
+        return Err(ContributronError::InvalidDateArithmetic(format!(
+            "year {year} is out of range"
+        )));
+    };
+    let days_until_end = 6 - week_start.row(december_31.weekday());
+    match december_31.checked_add_days(Days::new(days_until_end.into())) {
+        Some(end) => Ok(week_start.week_of(january_1)?..=end),
+        None => Err(ContributronError::InvalidDateArithmetic(format!(
+            "couldn't add {days_until_end} days to {december_31}"
+        ))),
+    }
+}
+
+/// Fill in whichever of `start_date` and `end_date` are missing
+/// (by default, the `platform.weeks` weeks ending at the start of this week),
+/// rounding the start down to the start of its week so that each column is one week
+/// (and warning about it in terms of `start_flag`, the flag that set it).
+#[inline]
+fn date_window(
+    start_flag: &str,
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+    allow_long_range: bool,
+    platform: PlatformConfig,
+) -> Result<RangeInclusive<NaiveDate>, ContributronError> {
+    let PlatformConfig {
+        week_start, weeks, ..
+    } = platform;
+    let days = platform.days();
+    let a_year = Days::new(u64::from(days)); // Rounded up to the nearest week.
+
+    let start_date = match start_date {
+        None => None,
+        Some(start_date) => {
+            let week = week_start.week_of(start_date)?;
+            if week != start_date {
+                eprintln!(
+                    "[warn] `{start_flag} {start_date}` is a {}, so rounding down to {} {week}",
+                    start_date.weekday(),
+                    week.weekday(),
+                );
+            }
+            Some(week)
+        }
+    };
+
+    let end_date = match (start_date, end_date) {
+        (_, Some(end_date)) => end_date,
+        (Some(start_date), None) => match start_date.checked_add_days(a_year) {
+            Some(some) => some,
+            None => {
+                return Err(ContributronError::InvalidDateArithmetic(format!(
+                    "couldn't add {a_year:?} to {start_date}"
+                )));
+            }
+        },
+        (None, None) => week_start.week_of(Utc::now().date_naive())?,
+    };
+
+    let start_date = match start_date {
+        Some(some) => some,
+        None => {
+            // The earliest start of a week no more than a year before the end:
+            let almost_a_year = Days::new(u64::from(days - 6));
+            let almost_a_year_ago = match end_date.checked_sub_days(almost_a_year) {
+                Some(some) => some,
+                None => {
+                    return Err(ContributronError::InvalidDateArithmetic(format!(
+                        "couldn't subtract {almost_a_year:?} from {end_date}"
+                    )));
+                }
+            };
+            week_start.week_of(almost_a_year_ago)?
+        }
+    };
+
+    if end_date < start_date {
+        return Err(ContributronError::InvalidArgument(format!(
+            "The end date ({end_date}) should not be before the start date ({start_date})."
+        )));
+    }
+    let span = end_date.signed_duration_since(start_date).num_days();
+    if !allow_long_range && span > i64::from(days) {
+        return Err(ContributronError::InvalidArgument(format!(
+            "{start_date} to {end_date} spans {span} days, but the contribution graph only shows {days} ({weeks} weeks); pass `--allow-long-range` to draw it anyway."
+        )));
+    }
+
+    Ok(start_date..=end_date)
+}
+
+/// Read an image (meant to be seven pixels tall) into a grid seven rows tall, one column per pixel column,
+/// ignoring any rows past the seventh and reading any missing rows as black.
+#[inline]
+#[must_use]
+pub fn image_to_columns(img: &DynamicImage) -> ContributionGrid {
+    rgba_to_masked_luma(img, 0)
+}
+
+/// Like `image_to_columns`, but black out every pixel
+/// whose alpha is below `alpha_threshold` (e.g. a logo's transparent background).
+#[inline]
+#[must_use]
+pub fn rgba_to_masked_luma(img: &DynamicImage, alpha_threshold: u8) -> ContributionGrid {
+    let mut grid = ContributionGrid::new(img.width() as usize, 7);
+    for (x, column) in (0..img.width()).zip(&mut grid.cells) {
+        for (y, luma) in (0..img.height()).zip(column) {
+            let pixel = img.get_pixel(x, y);
+            let [_, _, _, alpha] = pixel.0;
+            [*luma] = if alpha < alpha_threshold {
+                [0]
+            } else {
+                pixel.to_luma().0
+            };
+        }
+    }
+    grid
+}
+
+/// Convert a (color) image to grayscale.
+#[inline]
+#[must_use]
+pub fn to_grayscale(img: &DynamicImage, method: GrayscaleMethod) -> GrayImage {
+    match method {
+        GrayscaleMethod::Luminosity => img.to_luma8(),
+        GrayscaleMethod::Average | GrayscaleMethod::Lightness => {
+            let rgb = img.to_rgb8();
+            GrayImage::from_fn(rgb.width(), rgb.height(), |x, y| {
+                let Rgb(channels) = *rgb.get_pixel(x, y);
+                let [r, g, b] = channels.map(u16::from);
+                let luma = if method == GrayscaleMethod::Average {
+                    (r + g + b) / 3
+                } else {
+                    (r.max(g).max(b) + r.min(g).min(b)) / 2
+                };
+                Luma([luma as u8])
+            })
+        }
+    }
+}
+
+/// Linearly stretch every pixel so that the darkest becomes black and the brightest white.
+/// A flat image (all one shade) is left as is, with a warning.
+#[inline]
+pub fn auto_contrast(grid: &mut ContributionGrid) {
+    let (Some(min), Some(max)) = (
+        grid.cells.iter().flatten().copied().min(),
+        grid.cells.iter().flatten().copied().max(),
+    ) else {
+        return;
+    };
+    if min == max {
+        eprintln!("[warn] `--auto-contrast`: every pixel is {min}, so there's nothing to stretch");
+        return;
+    }
+    let range = u16::from(max - min);
+    for pixel in grid.cells.iter_mut().flatten() {
+        *pixel = (u16::from(*pixel - min) * u16::from(u8::MAX) / range) as u8;
+    }
+}
+
+/// Black out every pixel darker than `threshold`.
+#[inline]
+pub fn threshold_columns(grid: &mut ContributionGrid, threshold: u8) {
+    for pixel in grid.cells.iter_mut().flatten() {
+        if *pixel < threshold {
+            *pixel = 0;
+        }
+    }
+}
+
+/// Gamma-correct each pixel: `out = 255 * (in / 255) ^ gamma`.
+#[inline]
+pub fn gamma_correct(grid: &mut ContributionGrid, gamma: f32) {
+    for pixel in grid.cells.iter_mut().flatten() {
+        let normalized = f32::from(*pixel) / f32::from(u8::MAX);
+        *pixel = (normalized.powf(gamma) * f32::from(u8::MAX)).round() as u8;
+    }
+}
+
+/// Rotate an image clockwise.
+#[inline]
+#[must_use]
+pub fn rotate(img: &DynamicImage, rotation: Rotation) -> DynamicImage {
+    match rotation {
+        Rotation::Quarter => img.rotate90(),
+        Rotation::Half => img.rotate180(),
+        Rotation::ThreeQuarters => img.rotate270(),
+    }
+}
+
+/// Mirror an image across `axis`.
+#[inline]
+#[must_use]
+pub fn reflect(grid: &ContributionGrid, axis: Axis) -> ContributionGrid {
+    let cells = match axis {
+        Axis::Horizontal => grid.cells.iter().rev().cloned().collect(),
+        Axis::Vertical => grid
+            .iter_columns()
+            .map(|column| column.iter().rev().copied().collect())
+            .collect(),
+    };
+    ContributionGrid::from_columns(cells, grid.rows)
+}
+
+/// Swap black and white.
+#[inline]
+pub fn invert_columns(grid: &mut ContributionGrid) {
+    for pixel in grid.cells.iter_mut().flatten() {
+        *pixel = u8::MAX - *pixel;
+    }
+}
+
+/// Everything from the first column with any non-black pixel to the last (or nothing, if there's none).
+#[inline]
+#[must_use]
+pub fn crop_to_content(grid: &ContributionGrid) -> ContributionGrid {
+    let lit = |column: &Vec<u8>| column.iter().any(|&luma| luma > 0);
+    let cells = match (
+        grid.cells.iter().position(lit),
+        grid.cells.iter().rposition(lit),
+    ) {
+        (Some(left), Some(right)) => grid.cells.get(left..=right).unwrap_or_default().to_vec(),
+        _ => vec![],
+    };
+    ContributionGrid::from_columns(cells, grid.rows)
+}
+
+/// `grid` with `left` blank columns before and `right` after.
+#[inline]
+#[must_use]
+pub fn pad_columns(grid: &ContributionGrid, left: usize, right: usize) -> ContributionGrid {
+    let blank = vec![0; grid.rows];
+    ContributionGrid::from_columns(
+        iter::repeat_n(blank.clone(), left)
+            .chain(grid.cells.iter().cloned())
+            .chain(iter::repeat_n(blank, right))
+            .collect(),
+        grid.rows,
+    )
+}
+
+/// Black out the top `top` and bottom `bottom` rows of every column.
+#[inline]
+pub fn pad_rows(grid: &mut ContributionGrid, top: u8, bottom: u8) {
+    let bottom = grid.rows.saturating_sub(usize::from(bottom));
+    for column in &mut grid.cells {
+        for (row, pixel) in column.iter_mut().enumerate() {
+            if row < usize::from(top) || row >= bottom {
+                *pixel = 0;
+            }
+        }
+    }
+}
+
+/// Prepend blank columns to `grid`, either `offset` of them
+/// or however many `align` implies (with neither, none) within a graph `weeks` columns wide.
+/// # Errors
+/// If the image wouldn't fit within the contribution graph after the offset.
+#[inline]
+pub fn offset_columns(
+    grid: &ContributionGrid,
+    offset: Option<usize>,
+    align: Option<Align>,
+    weeks: u16,
+) -> Result<ContributionGrid, ContributronError> {
+    let weeks = usize::from(weeks);
+    let width = grid.cols;
+    let offset = match (offset, align) {
+        (None, None) => return Ok(grid.clone()),
+        (Some(offset), _) => offset,
+        (None, Some(Align::Left)) => 0,
+        (None, Some(Align::Center)) => weeks.saturating_sub(width) / 2,
+        (None, Some(Align::Right)) => weeks.saturating_sub(width),
+    };
+    if offset + width > weeks {
+        return Err(ContributronError::InvalidArgument(
+            if let Some(max_offset) = weeks.checked_sub(width) {
+                format!(
+                    "An offset of {offset} would push the image ({width} columns wide) past the {weeks} columns of the contribution graph; try `--offset {max_offset}` or less, or `--align right`."
+                )
+            } else {
+                format!(
+                    "The image ({width} columns wide) is wider than the {weeks} columns of the contribution graph, so it can't be offset; crop or resize it first."
+                )
+            },
+        ));
+    }
+    Ok(pad_columns(grid, offset, 0))
+}
+
+/// Exactly `n` copies of `grid`, each followed by a blank column,
+/// padded with blank columns to the width of the contribution graph (`weeks` columns).
+/// If that many copies won't fit, draw (with a warning) as many as will.
+/// With `n` of 0, `grid` is returned unchanged, to be repeated endlessly.
+#[inline]
+#[must_use]
+pub fn repeat_columns(grid: &ContributionGrid, n: usize, weeks: u16) -> ContributionGrid {
+    if n == 0 {
+        return grid.clone();
+    }
+    let weeks = usize::from(weeks);
+    let width = grid.cols + 1;
+    let fit = (weeks / width).max(1);
+    let n = if n > fit {
+        eprintln!(
+            "[warn] `--repeat {n}`: only {fit} copies of the image ({} columns wide, plus a blank column) fit in {weeks} columns",
+            width - 1,
+        );
+        fit
+    } else {
+        n
+    };
+    let blank = vec![0; grid.rows];
+    let mut repeated: Vec<Vec<u8>> = iter::repeat_n(
+        grid.cells.iter().cloned().chain(iter::once(blank.clone())),
+        n,
+    )
+    .flatten()
+    .collect();
+    let () = repeated.resize(repeated.len().max(weeks), blank);
+    ContributionGrid::from_columns(repeated, grid.rows)
+}
+
+/// Light every black pixel at `brightness`, except on `blank_rows` (see `WeekStart::row`).
+#[inline]
+pub fn fill_background(grid: &mut ContributionGrid, brightness: u8, blank_rows: &[usize]) {
+    for column in &mut grid.cells {
+        for (row, pixel) in column.iter_mut().enumerate() {
+            if *pixel == 0 && !blank_rows.contains(&row) {
+                *pixel = brightness;
+            }
+        }
+    }
+}
+
+/// The commit that `git.reference` points to, if any.
+#[inline]
+fn head_commit(git: &GitInfo) -> Option<git2::Oid> {
+    let reference = git.repo.find_reference(git.reference).ok()?;
+    reference.peel_to_commit().ok().map(|commit| commit.id())
+}
+
+/// Tag the first and last commits made since `git.reference` pointed to `before`
+/// (lightweight tags named `<prefix>-start` and `<prefix>-end`),
+/// replacing any existing tags with those names if `force` is set.
+/// Warns and tags nothing if no commits were made.
+/// # Errors
+/// If either tag already exists (without `force`) or can't be created.
+#[inline]
+pub fn tag_boundaries(
+    git: &GitInfo,
+    before: Option<git2::Oid>,
+    prefix: &str,
+    force: bool,
+) -> Result<(), ContributronError> {
+    let last = match head_commit(git) {
+        Some(last) if Some(last) != before => last,
+        _ => {
+            eprintln!("[warn] No new commits to tag with `--tag-prefix {prefix}`");
+            return Ok(());
+        }
+    };
+    let last = match git.repo.find_commit(last) {
+        Ok(ok) => ok,
+        Err(source) => {
+            return Err(ContributronError::Git {
+                context: format!("Couldn't find the last commit made (OID {last})"),
+                source,
+            });
+        }
+    };
+    let mut first = last.clone();
+    while let Ok(parent) = first.parent(0) {
+        if Some(parent.id()) == before {
+            break;
+        }
+        first = parent;
+    }
+    for (suffix, commit) in [("start", &first), ("end", &last)] {
+        let name = format!("{prefix}-{suffix}");
+        match git.repo.tag_lightweight(&name, commit.as_object(), force) {
+            Ok(_) => {}
+            Err(source) => {
+                return Err(ContributronError::Git {
+                    context: format!("Couldn't tag {} as `{name}`", commit.id()),
+                    source,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The latest day (in each commit's own time zone) of any commit in `reference`'s history,
+/// or `None` if it has no commits yet.
+/// # Errors
+/// See `count_commits_per_day`.
+#[inline]
+pub fn last_committed_date(
+    repo: &git2::Repository,
+    reference: &str,
+) -> Result<Option<NaiveDate>, ContributronError> {
+    Ok(count_commits_per_day(repo, reference)?.into_keys().max())
+}
+
+/// Number of commits on each day from `from` to `to` (inclusive) in the history of `repo`'s `HEAD`,
+/// scaled (and rounded) so that the busiest day has `scale`, leaving out days without any.
+/// # Errors
+/// See `count_commits_per_day`.
+#[inline]
+pub fn git_log_to_pattern(
+    repo: &git2::Repository,
+    from: NaiveDate,
+    to: NaiveDate,
+    scale: u8,
+) -> Result<HashMap<NaiveDate, u8>, ContributronError> {
+    let counts: HashMap<NaiveDate, u32> = count_commits_per_day(repo, "HEAD")?
+        .into_iter()
+        .filter(|&(date, _)| from <= date && date <= to)
+        .collect();
+    let busiest = u64::from(counts.values().copied().max().unwrap_or(1));
+    Ok(counts
+        .into_iter()
+        .map(|(date, count)| {
+            let scaled = (u64::from(count) * u64::from(scale) + busiest / 2) / busiest;
+            (date, u8::try_from(scaled).unwrap_or(u8::MAX))
+        })
+        .collect())
+}
+
+/// How many commits in `reference`'s history fall on each day (in each commit's own time zone),
+/// leaving out days without any.
+/// # Errors
+/// If the reference doesn't exist or its history can't be walked.
+#[inline]
+pub fn count_commits_per_day(
+    repo: &git2::Repository,
+    reference: &str,
+) -> Result<HashMap<NaiveDate, u32>, ContributronError> {
+    let head = match repo.find_reference(reference) {
+        Ok(ok) => ok,
+        Err(source) => {
+            return Err(ContributronError::ReferenceNotFound {
+                reference: reference.to_owned(),
+                source,
+            });
+        }
+    };
+    let Ok(head) = head.peel_to_commit() else {
+        return Ok(HashMap::new());
+    };
+    let mut revwalk = match repo.revwalk() {
+        Ok(ok) => ok,
+        Err(source) => {
+            return Err(ContributronError::Git {
+                context: "Couldn't walk the repo's history".to_owned(),
+                source,
+            });
+        }
+    };
+    match revwalk.push(head.id()) {
+        Ok(()) => {}
+        Err(source) => {
+            return Err(ContributronError::Git {
+                context: format!("Couldn't walk the history of `{reference}`"),
+                source,
+            });
+        }
+    }
+    let mut counts = HashMap::new();
+    for oid in revwalk {
+        let commit = match oid.and_then(|oid| repo.find_commit(oid)) {
+            Ok(ok) => ok,
+            Err(source) => {
+                return Err(ContributronError::Git {
+                    context: format!("Couldn't walk the history of `{reference}`"),
+                    source,
+                });
+            }
+        };
+        let time = commit.time();
+        let local = time.seconds() + i64::from(time.offset_minutes()) * 60;
+        let Some(date) = DateTime::from_timestamp(local, 0).map(|local| local.date_naive()) else {
+            return Err(ContributronError::InvalidDateArithmetic(format!(
+                "commit {} has an out-of-range time ({} seconds since the epoch)",
+                commit.id(),
+                time.seconds(),
+            )));
+        };
+        *counts.entry(date).or_insert(0) += 1;
+    }
+    Ok(counts)
+}
+
+/// Create a fresh Git repository at `repo`,
+/// first removing anything already there if `overwrite` is set.
+/// # Errors
+/// If `repo` already exists (and `overwrite` is not set) or can't be created.
+#[inline]
+pub fn init_repo(repo: &Path, overwrite: bool) -> Result<git2::Repository, ContributronError> {
+    // Convert the repository path to an absolute path:
+    let repo = match path::absolute(repo) {
+        Ok(ok) => ok,
+        Err(source) => {
+            return Err(ContributronError::Io {
+                context: format!("Couldn't make `{}` absolute", repo.to_string_lossy()),
+                source,
+            });
+        }
+    };
+
+    // Create the folders nesting the repo folder, if any,
+    // before the repo itself to avoid a race condition:
+    if let Some(parent) = repo.parent() {
+        match fs::create_dir_all(parent) {
+            Ok(()) => {}
+            Err(source) => {
+                return Err(ContributronError::Io {
+                    context: format!("Couldn't ensure that `{}` exists", parent.to_string_lossy()),
+                    source,
+                });
+            }
+        }
+    }
+
+    if overwrite {
+        match fs::remove_dir_all(&repo) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(source) => {
+                return Err(ContributronError::Io {
+                    context: format!("Couldn't remove `{}`", repo.to_string_lossy()),
+                    source,
+                });
+            }
+        }
+    }
+
+    // Try to create the repo folder, exiting on failure,
+    // instead of checking its existence and then trying
+    // (to avoid a race condition between those steps):
+    match fs::create_dir(&repo) {
+        Ok(()) => {}
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            return Err(ContributronError::RepoExists(repo));
+        }
+        Err(source) => {
+            return Err(ContributronError::Io {
+                context: format!("Couldn't create `{}`", repo.to_string_lossy()),
+                source,
+            });
+        }
+    }
+
+    match git2::Repository::init(&repo) {
+        Ok(ok) => Ok(ok),
+        Err(source) => Err(ContributronError::RepoInit { path: repo, source }),
+    }
+}
+
+/// Open an existing Git repository at `repo` to add commits to `reference`.
+/// # Errors
+/// If there's no repository at `repo` or it has no such reference.
+#[inline]
+pub fn open_repo(repo: &Path, reference: &str) -> Result<git2::Repository, ContributronError> {
+    let opened = match git2::Repository::open(repo) {
+        Ok(ok) => ok,
+        Err(source) => {
+            return Err(ContributronError::RepoOpen {
+                path: repo.to_path_buf(),
+                source,
+            });
+        }
+    };
+    match opened.find_reference(reference) {
+        Ok(_) => {}
+        Err(e) => {
+            return Err(ContributronError::InvalidArgument(format!(
+                "`{}` has no reference `{reference}` to append to ({e}); run without `--append` first to create it",
+                repo.to_string_lossy(),
+            )));
+        }
+    }
+    Ok(opened)
+}
+
+/// Record (in `UNDO_FILE`) where `git.reference` pointed before this run, for `undo`.
+/// With no commit before (i.e. a fresh repository), undoing will delete the reference instead,
+/// unless it's still unborn (nothing was drawn), in which case any undo file is removed.
+/// # Errors
+/// If the reference can't be resolved or the file can't be written.
+#[inline]
+fn save_undo(git: &GitInfo, before: Option<git2::Oid>) -> Result<(), ContributronError> {
+    let path = git.repo.path().join(UNDO_FILE);
+    // Save the branch itself, not e.g. `HEAD`, so that undoing doesn't detach anything:
+    let name = match (
+        git.repo
+            .find_reference(git.reference)
+            .and_then(|reference| reference.resolve()),
+        before,
+    ) {
+        (Ok(reference), _) => reference.name().unwrap_or(git.reference).to_owned(),
+        // Still unborn, so nothing was drawn and there's nothing to undo:
+        (Err(_), None) => {
+            return match fs::remove_file(&path) {
+                Ok(()) => Ok(()),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+                Err(source) => Err(ContributronError::Io {
+                    context: format!("Couldn't remove `{}`", path.to_string_lossy()),
+                    source,
+                }),
+            };
+        }
+        (Err(source), Some(_)) => {
+            return Err(ContributronError::ReferenceNotFound {
+                reference: git.reference.to_owned(),
+                source,
+            });
+        }
+    };
+    // A blank first line means the reference had no commits before, so undoing deletes it:
+    let before = before.map_or_else(String::new, |oid| oid.to_string());
+    match fs::write(&path, format!("{before}\n{name}\n")) {
+        Ok(()) => Ok(()),
+        Err(source) => Err(ContributronError::Io {
+            context: format!("Couldn't write `{}`", path.to_string_lossy()),
+            source,
+        }),
+    }
+}
+
+/// Reset whichever reference the last run on `repo` drew on to where it pointed before
+/// (as recorded in `UNDO_FILE`), then forget it, returning the reference and the commit it's back on
+/// (or `None` if it had no commits before, in which case it's deleted).
+/// # Errors
+/// If there's no record of a run to undo,
+/// or if the commit to go back to no longer exists (e.g. after `git gc`).
+#[inline]
+pub fn undo(repo: &Path) -> Result<(String, Option<git2::Oid>), ContributronError> {
+    let opened = match git2::Repository::open(repo) {
+        Ok(ok) => ok,
+        Err(source) => {
+            return Err(ContributronError::RepoOpen {
+                path: repo.to_path_buf(),
+                source,
+            });
+        }
+    };
+    let path = opened.path().join(UNDO_FILE);
+    let contents = match fs::read_to_string(&path) {
+        Ok(ok) => ok,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            return Err(ContributronError::NothingToUndo(repo.to_path_buf()));
+        }
+        Err(source) => {
+            return Err(ContributronError::Io {
+                context: format!("Couldn't read `{}`", path.to_string_lossy()),
+                source,
+            });
+        }
+    };
+    let mut lines = contents.lines();
+    let (Some(oid), Some(name)) = (lines.next(), lines.next()) else {
+        return Err(ContributronError::InvalidArgument(format!(
+            "`{}` should have a commit on one line and a reference on the next, but it has `{contents}`",
+            path.to_string_lossy(),
+        )));
+    };
+    let oid = if oid.is_empty() {
+        match opened
+            .find_reference(name)
+            .and_then(|mut reference| reference.delete())
+        {
+            Ok(()) => {}
+            Err(source) => {
+                return Err(ContributronError::Git {
+                    context: format!("Couldn't delete `{name}`"),
+                    source,
+                });
+            }
+        }
+        None
+    } else {
+        let oid = match git2::Oid::from_str(oid) {
+            Ok(ok) => ok,
+            Err(e) => {
+                return Err(ContributronError::InvalidArgument(format!(
+                    "`{}` should start with a commit, but it starts with `{oid}` ({e})",
+                    path.to_string_lossy(),
+                )));
+            }
+        };
+        match opened.find_commit(oid) {
+            Ok(_) => {}
+            Err(source) => {
+                return Err(ContributronError::Git {
+                    context: format!(
+                        "Couldn't find commit {oid} to reset `{name}` to (was it garbage-collected?)"
+                    ),
+                    source,
+                });
+            }
+        }
+        match opened.reference(name, oid, true, "contributron undo") {
+            Ok(_) => {}
+            Err(source) => {
+                return Err(ContributronError::Git {
+                    context: format!("Couldn't reset `{name}` to {oid}"),
+                    source,
+                });
+            }
+        }
+        Some(oid)
+    };
+    match fs::remove_file(&path) {
+        Ok(()) => Ok((name.to_owned(), oid)),
+        Err(source) => Err(ContributronError::Io {
+            context: format!("Couldn't remove `{}`", path.to_string_lossy()),
+            source,
+        }),
+    }
+}
+
+/// Summarize `plan` and, if asked to (`confirm_first` or `require_confirm`),
+/// ask on standard error whether to go ahead, reading the answer from standard input.
+/// # Errors
+/// If the answer isn't yes, or if `require_confirm` is set and standard input isn't a terminal.
+#[inline]
+fn confirm(
+    plan: &CommitPlan,
+    repo: &Path,
+    yes: bool,
+    require_confirm: bool,
+    confirm_first: bool,
+) -> Result<(), ContributronError> {
+    if yes || !(confirm_first || require_confirm) {
+        return Ok(());
+    }
+    let stdin = io::stdin();
+    if !stdin.is_terminal() {
+        if require_confirm {
+            return Err(ContributronError::InvalidArgument(
+                "`--require-confirm` needs a terminal to ask for confirmation (pass `--yes` to skip it)".to_owned(),
+            ));
+        }
+        return Ok(());
+    }
+
+    let total: usize = plan
+        .entries
+        .iter()
+        .map(|entry| usize::from(entry.commit_count))
+        .sum();
+    if let (Some(first), Some(last)) = (plan.entries.first(), plan.entries.last()) {
+        eprintln!(
+            "{total} commits from {} to {} in `{}`",
+            first.date,
+            last.date,
+            repo.to_string_lossy(),
+        );
+    }
+    eprint!("Proceed? [y/N] ");
+    let mut answer = String::new();
+    match stdin.read_line(&mut answer) {
+        Ok(_) => {}
+        Err(source) => {
+            return Err(ContributronError::Io {
+                context: "Couldn't read an answer from standard input".to_owned(),
+                source,
+            });
+        }
+    }
+    if matches!(answer.trim(), "y" | "Y" | "yes" | "Yes" | "YES") {
+        Ok(())
+    } else {
+        Err(ContributronError::Cancelled)
+    }
+}
+
+/// Draw once with `args` (see `run`), or, with `--watch`, every time the image changes (see `watch`).
+/// # Errors
+/// See `run` and `watch`.
+#[inline]
+fn run_or_watch(args: Args) -> Result<(), ContributronError> {
+    if args.watch { watch(&args) } else { run(args) }
+}
+
+/// Everything the command-line tool does, given its arguments (starting with the program name).
+/// # Errors
+/// See `run` and `parse_args`.
+#[inline]
+pub fn run_cli<I, T>(argv: I) -> Result<(), ContributronError>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<std::ffi::OsString> + Clone,
+{
+    match parse_args(argv)? {
+        Commands::Generate(generate) => run_or_watch(Args::from(*generate)),
+        Commands::Preview(preview) => run_or_watch(Args::from(*preview)),
+        Commands::Plan(plan) => run_or_watch(Args::from(*plan)),
+        Commands::InitConfig { config } => {
+            let Some(path) = config.or_else(default_config_path) else {
+                return Err(ContributronError::InvalidArgument(
+                    "Couldn't find the home directory; please pass `--config <path>`.".to_owned(),
+                ));
+            };
+            let () = init_config(&path)?;
+            println!("Wrote `{}`", path.to_string_lossy());
+            Ok(())
+        }
+        Commands::Completions { shell } => {
+            let () = write_completions(shell, &mut io::stdout());
+            Ok(())
+        }
+        Commands::Man => write_man_page(&mut io::stdout()),
+        Commands::Undo(UndoArgs { repo }) => {
+            let () = match undo(&repo)? {
+                (reference, Some(oid)) => println!("Reset `{reference}` to {oid}"),
+                (reference, None) => println!("Deleted `{reference}` (it had no commits before)"),
+            };
+            Ok(())
+        }
+        Commands::VerifyImage(VerifyImageArgs {
+            image,
+            scale,
+            threshold,
+            platform,
+        }) => {
+            let report = verify_image(&image, scale, threshold, platform.config().weeks)?;
+            println!("{report}");
+            for warning in &report.warnings {
+                eprintln!("[warn] {warning}");
+            }
+            Ok(())
+        }
+        Commands::ListPlatforms => {
+            print!("{}", platform_table());
+            Ok(())
+        }
+        Commands::ListProfiles { config } => {
+            let Some(path) = config.or_else(default_config_path) else {
+                return Err(ContributronError::InvalidArgument(
+                    "Couldn't find the home directory; please pass `--config <path>`.".to_owned(),
+                ));
+            };
+            for name in load_config(&path)?.profiles.keys() {
+                println!("{name}");
+            }
+            Ok(())
+        }
+    }
+}
+
+/// With `--animate`, one column per week of `dates`,
+/// drawing each calendar year (from the week of January 1) with the next of `--image` in turn
+/// (starting over if there are more years than images),
+/// repeated as needed (see `repeat_pattern`) to fill that year.
+/// # Errors
+/// If any image can't be loaded (see `load_columns`).
+#[inline]
+fn animate_columns(
+    args: &Args,
+    dates: &RangeInclusive<NaiveDate>,
+) -> Result<ContributionGrid, ContributronError> {
+    let mut columns = vec![];
+    let mut start = *dates.start();
+    for image in args.image.iter().cycle() {
+        if start > *dates.end() {
+            break;
+        }
+        // Each week belongs to the year its last day is in, so that January 1 starts a year:
+        let year = match start.checked_add_days(Days::new(6)) {
+            Some(last_day) => last_day.year(),
+            None => start.year(),
+        };
+        let next_year = match NaiveDate::from_ymd_opt(year + 1, 1, 1) {
+            Some(january_1) => platform_config(args).week_start.week_of(january_1)?,
+            None => {
+                return Err(ContributronError::InvalidDateArithmetic(format!(
+                    "year {} is out of range",
+                    year + 1,
+                )));
+            }
+        };
+        let Some(end) = next_year.pred_opt() else {
+            return Err(ContributronError::InvalidDateArithmetic(format!(
+                "couldn't find the day before {next_year}"
+            )));
+        };
+        let n_days = count_days(&(start..=end.min(*dates.end())))?;
+        let pixels = repeat_pattern(&load_columns(args, Some(image))?, n_days);
+        columns.extend(weeks(&pixels).cells);
+        start = next_year;
+    }
+    Ok(ContributionGrid::from_columns(columns, 7))
+}
+
+/// Render `--text` or load, check, and preprocess `image` (one of `--image`) into a grid, one column per week,
+/// as described (in order) in `run`.
+/// # Errors
+/// If the image can't be read or (after any resizing or rotation) isn't seven pixels tall,
+/// or `--qr` can't be encoded.
+#[inline]
+fn load_columns(args: &Args, image: Option<&Path>) -> Result<ContributionGrid, ContributronError> {
+    let Args {
+        ref text,
+        letter_brightness,
+        spacing,
+        noise,
+        pattern,
+        pattern_period,
+        ref qr,
+        seed,
+        crop_to_content: crop,
+        padding,
+        padding_left,
+        padding_right,
+        padding_rows_top,
+        padding_rows_bottom,
+        offset,
+        align,
+        repeat,
+        threshold,
+        curve,
+        posterize: levels,
+        background,
+        weekday_only,
+        weekend_only,
+        weekend_boost,
+        invert,
+        gamma,
+        auto_contrast: stretch,
+        flip_h,
+        flip_v,
+        ..
+    } = *args;
+    let weeks = platform_config(args).weeks;
+    let mut columns = match (text.as_deref(), noise, pattern) {
+        (Some(text), _, _) => TextPattern {
+            text: text.to_owned(),
+            brightness: letter_brightness.unwrap_or(200),
+            spacing: spacing.unwrap_or(1),
+        }
+        .columns(),
+        (None, Some(density), _) => NoisePattern {
+            density,
+            seed: seed.unwrap_or_else(rand::random),
+            weeks,
+        }
+        .columns(),
+        (None, None, Some(kind)) => BuiltinPattern {
+            kind,
+            brightness: u8::MAX,
+            period: pattern_period.unwrap_or(1),
+            seed: seed.unwrap_or_else(rand::random),
+            weeks,
+        }
+        .columns(),
+        (None, None, None) => match (qr, image) {
+            (Some(qr), _) => qr_to_columns(qr, weeks)?,
+            (None, Some(image)) => load_image(args, image)?,
+            (None, None) => {
+                return Err(ContributronError::InvalidArgument(
+                    "An `--image` (or `--text`, `--noise`, `--pattern`, `--qr`, `--per-day-file`, or `--from-git-log`) is required (unless replaying a plan with `--from-plan`)."
+                        .to_owned(),
+                ));
+            }
+        },
+    };
+    if invert {
+        let () = invert_columns(&mut columns);
+    }
+    if stretch {
+        let () = auto_contrast(&mut columns);
+    }
+    if threshold > 0 {
+        let () = threshold_columns(&mut columns, threshold);
+    }
+    if gamma != 1. {
+        let () = gamma_correct(&mut columns, gamma);
+    }
+    if flip_h {
+        columns = reflect(&columns, Axis::Horizontal);
+    }
+    if flip_v {
+        columns = reflect(&columns, Axis::Vertical);
+    }
+    if crop {
+        columns = crop_to_content(&columns);
+    }
+    let mut columns = pad_columns(
+        &columns,
+        padding_left.or(padding).unwrap_or(0),
+        padding_right.or(padding).unwrap_or(0),
+    );
+    let () = pad_rows(&mut columns, padding_rows_top, padding_rows_bottom);
+    let columns = offset_columns(&columns, offset, align, weeks)?;
+    let mut columns = repeat_columns(&columns, repeat, weeks);
+    if curve != Curve::Linear {
+        for pixel in columns.cells.iter_mut().flatten() {
+            // Scaled to 255 here, and to `--scale` later (after dithering):
+            *pixel = apply_curve(*pixel, u8::MAX, curve);
+        }
+    }
+    if let Some(levels) = levels {
+        for pixel in columns.cells.iter_mut().flatten() {
+            *pixel = posterize(*pixel, levels);
+        }
+    }
+    let weekend = platform_config(args).week_start.weekend_rows();
+    let blank_rows: Vec<usize> = (0..columns.rows)
+        .filter(|row| {
+            (weekday_only && weekend.contains(row)) || (weekend_only && !weekend.contains(row))
+        })
+        .collect();
+    for column in &mut columns.cells {
+        for &row in &blank_rows {
+            if let Some(pixel) = column.get_mut(row) {
+                *pixel = 0;
+            }
+        }
+    }
+    if weekend_boost != 1. {
+        for column in &mut columns.cells {
+            for row in weekend {
+                if let Some(pixel) = column.get_mut(row) {
+                    *pixel = (f32::from(*pixel) * weekend_boost).min(255.) as u8;
+                }
+            }
+        }
+    }
+    // Last, so that the background is even (not boosted on weekends) and blank days stay blank:
+    if background > 0 {
+        let () = fill_background(&mut columns, background, &blank_rows);
+    }
+    Ok(columns)
+}
+
+/// Load, check, and preprocess `image` (one of `--image`) up to (but not including) auto-contrast.
+/// # Errors
+/// If the image can't be read or (after any resizing or rotation) isn't seven pixels tall.
+#[inline]
+fn load_image(args: &Args, image: &Path) -> Result<ContributionGrid, ContributronError> {
+    let Args {
+        resize,
+        resize_filter,
+        brightness,
+        contrast,
+        grayscale_method,
+        alpha_threshold,
+        rotate: rotation,
+        blur,
+        ..
+    } = *args;
+
+    let metadata = match image::open(image) {
+        Ok(ok) => ok,
+        Err(source) => {
+            return Err(ContributronError::ImageOpen {
+                path: image.to_path_buf(),
+                source,
+            });
+        }
+    };
+    let metadata = match rotation {
+        Some(rotation) => rotate(&metadata, rotation),
+        None => metadata,
+    };
+    let metadata = if resize {
+        let (width, height) = metadata.dimensions();
+        let new_width = (u64::from(width) * 7).div_ceil(u64::from(height).max(1));
+        let new_width = match u32::try_from(new_width) {
+            Ok(ok) => ok.max(1),
+            Err(e) => {
+                return Err(ContributronError::InvalidArgument(format!(
+                    "Ridiculously wide image: couldn't resize `{}` to {new_width}x7: {e}",
+                    image.to_string_lossy(),
+                )));
+            }
+        };
+        metadata.resize_exact(new_width, 7, resize_filter.into())
+    } else {
+        metadata
+    };
+    let metadata = match blur {
+        Some(sigma) => metadata.blur(sigma),
+        None => metadata,
+    };
+    let metadata = if metadata.color().has_color() {
+        let gray = to_grayscale(&metadata, grayscale_method);
+        if metadata.color().has_alpha() {
+            // Keep the alpha channel for `--alpha-threshold`:
+            DynamicImage::ImageLumaA8(GrayAlphaImage::from_fn(
+                gray.width(),
+                gray.height(),
+                |x, y| {
+                    let Luma([luma]) = *gray.get_pixel(x, y);
+                    let [_, _, _, alpha] = metadata.get_pixel(x, y).0;
+                    LumaA([luma, alpha])
+                },
+            ))
+        } else {
+            DynamicImage::ImageLuma8(gray)
+        }
+    } else {
+        metadata
+    };
+    let metadata = if brightness == 0 {
+        metadata
+    } else {
+        metadata.brighten(brightness.into())
+    };
+    let metadata = if contrast == 0. {
+        metadata
+    } else {
+        metadata.adjust_contrast(contrast)
+    };
+    let (width, height) = metadata.dimensions();
+    if height != 7 {
+        return Err(ContributronError::InvalidDimensions {
+            path: image.to_path_buf(),
+            width,
+            height,
+        });
+    }
+
+    Ok(if metadata.color().has_alpha() {
+        rgba_to_masked_luma(&metadata, alpha_threshold)
+    } else {
+        image_to_columns(&metadata)
+    })
+}
+
+/// Check arguments that clap can't (on its own) before doing anything else.
+/// # Errors
+/// If any argument is out of range or contradicts another.
+#[inline]
+fn validate(args: &Args) -> Result<(), ContributronError> {
+    let Args {
+        ref text,
+        letter_brightness,
+        spacing,
+        noise,
+        pattern,
+        pattern_period,
+        blur,
+        gamma,
+        max_commits,
+        min_commits,
+        overwrite,
+        append,
+        update,
+        dry_run,
+        watch,
+        ref image,
+        animate,
+        commit:
+            CommitArgs {
+                jobs,
+                in_memory,
+                ref gpg_key_id,
+                ..
+            },
+        weekend_boost,
+        spread_window,
+        gaussian_time,
+        ref readme_template,
+        ..
+    } = *args;
+    #[cfg(feature = "output-json")]
+    let checkpoint = args.checkpoint.is_some();
+    #[cfg(not(feature = "output-json"))]
+    let checkpoint = false;
+    if in_memory && checkpoint {
+        return Err(ContributronError::InvalidArgCombination(
+            "`--in-memory` writes no commits until the end, so a `--checkpoint` (and `--resume` from it) could point to commits that were never written"
+                .to_owned(),
+        ));
+    }
+    if jobs > 1 {
+        if in_memory {
+            return Err(ContributronError::InvalidArgCombination(format!(
+                "`--in-memory` keeps objects where only one repository handle can see them, so it can't be used with `--jobs {jobs}`"
+            )));
+        }
+        for (name, used) in [
+            ("--gpg-key-id", gpg_key_id.is_some()),
+            ("--readme-template", readme_template.is_some()),
+            ("--checkpoint", checkpoint),
+        ] {
+            if used {
+                return Err(ContributronError::InvalidArgCombination(format!(
+                    "`{name}` needs every commit's parent as it's made, so it can't be used with `--jobs {jobs}`"
+                )));
+            }
+        }
+    }
+    if animate && image.is_empty() {
+        return Err(ContributronError::InvalidArgCombination(
+            "`--animate` only applies to `--image`, which wasn't given".to_owned(),
+        ));
+    }
+    if !animate && image.len() > 1 {
+        return Err(ContributronError::InvalidArgCombination(format!(
+            "{} images given, but only `--animate` draws more than one",
+            image.len(),
+        )));
+    }
+    if text.is_none() {
+        for (name, used) in [
+            ("--letter-brightness", letter_brightness.is_some()),
+            ("--spacing", spacing.is_some()),
+        ] {
+            if used {
+                return Err(ContributronError::InvalidArgCombination(format!(
+                    "`{name}` only applies to `--text`, which wasn't given"
+                )));
+            }
+        }
+    }
+    if watch && !(overwrite || append || update || dry_run) {
+        return Err(ContributronError::InvalidArgCombination(
+            "`--watch` draws again on every change, so it needs `--force`, `--append`, `--update`, or `--dry-run`"
+                .to_owned(),
+        ));
+    }
+    if pattern.is_none() && pattern_period.is_some() {
+        return Err(ContributronError::InvalidArgCombination(
+            "`--pattern-period` only applies to `--pattern`, which wasn't given".to_owned(),
+        ));
+    }
+    if let Some(sigma) = blur
+        && (sigma.is_nan() || sigma <= 0.)
+    {
+        return Err(ContributronError::InvalidArgument(format!(
+            "`--blur` should be positive, but it was {sigma}."
+        )));
+    }
+    if let Some(density) = noise
+        && !(density.is_finite() && density > 0.)
+    {
+        return Err(ContributronError::InvalidArgument(format!(
+            "`--noise` should be positive, but it was {density}."
+        )));
+    }
+    if !(spread_window > 0. && spread_window <= 24.) {
+        return Err(ContributronError::InvalidArgument(format!(
+            "`--spread-window` should be more than 0 and at most 24 (hours), but it was {spread_window}."
+        )));
+    }
+    if let Some(sigma) = gaussian_time
+        && !(sigma.is_finite() && sigma > 0.)
+    {
+        return Err(ContributronError::InvalidArgument(format!(
+            "`--gaussian-time` should be positive, but it was {sigma}."
+        )));
+    }
+    if !(weekend_boost > 0. && weekend_boost <= 10.) {
+        return Err(ContributronError::InvalidArgument(format!(
+            "`--weekend-boost` should be more than 0 and at most 10, but it was {weekend_boost}."
+        )));
+    }
+    if gamma.is_nan() || gamma <= 0. {
+        return Err(ContributronError::InvalidArgument(format!(
+            "`--gamma` should be positive, but it was {gamma}."
+        )));
+    }
+    if let Some(max_commits) = max_commits
+        && max_commits < min_commits
+    {
+        return Err(ContributronError::InvalidArgCombination(format!(
+            "`--max-commits {max_commits}` is less than `--min-commits {min_commits}`"
+        )));
+    }
+    Ok(())
+}
+
+/// The layout of `--platform`'s graph, starting each week on `--week-start` if that's given.
+#[inline]
+fn platform_config(args: &Args) -> PlatformConfig {
+    let mut config = args.platform.config();
+    if let Some(week_start) = args.week_start {
+        config.week_start = week_start;
+    }
+    config
+}
+
+/// The days to draw on, from `--start-date` (or `--since`), `--end-date`, and `--year`.
+/// # Errors
+/// See `date_window` and `year_window`.
+#[inline]
+fn date_range(args: &Args) -> Result<RangeInclusive<NaiveDate>, ContributronError> {
+    let Args {
+        start_date,
+        since,
+        end_date,
+        allow_long_range,
+        year,
+        ..
+    } = *args;
+    let (start_flag, start_date) = match since {
+        Some(since) => ("--since", Some(since)),
+        None => ("--start-date", start_date),
+    };
+    let (start_date, end_date) = match year {
+        None => (start_date, end_date),
+        Some(year) => {
+            if year > Utc::now().year() {
+                eprintln!("[warn] `--year {year}` is in the future");
+            }
+            let whole_year = year_window(year, platform_config(args).week_start)?;
+            (
+                start_date.or(Some(*whole_year.start())),
+                end_date.or(Some(*whole_year.end())),
+            )
+        }
+    };
+    date_window(
+        start_flag,
+        start_date,
+        end_date,
+        allow_long_range,
+        platform_config(args),
+    )
+}
+
+/// Validate and draw, exactly as the command-line tool does.
+///
+/// Images are processed in this order:
+/// load → rotate → resize → blur → grayscale → brightness → contrast → invert → auto-contrast → threshold → gamma → flip → crop → pad → offset → repeat → curve → posterize → weekdays → background → scale → dither.
+/// Text (`--text`), noise (`--noise`), built-in patterns (`--pattern`), and QR codes (`--qr`)
+/// are generated in place of the first seven steps.
+/// With `--animate`, each image goes through every step but the last two on its own,
+/// and is repeated across its own year (see `animate_columns`).
+/// # Errors
+/// If any argument is invalid, the image can't be drawn
+/// (e.g. it doesn't exist or has the wrong dimensions),
+/// or anything goes wrong with Git.
+#[inline]
+pub fn run(args: Args) -> Result<(), ContributronError> {
+    let () = validate(&args)?;
+    if let Some(ref path) = args.generate_workflow {
+        let workflow = render_workflow(&args)?;
+        if let Some(parent) = path.parent() {
+            match fs::create_dir_all(parent) {
+                Ok(()) => {}
+                Err(source) => {
+                    return Err(ContributronError::Io {
+                        context: format!(
+                            "Couldn't ensure that `{}` exists",
+                            parent.to_string_lossy()
+                        ),
+                        source,
+                    });
+                }
+            }
+        }
+        return match fs::write(path, workflow) {
+            Ok(()) => Ok(()),
+            Err(source) => Err(ContributronError::Io {
+                context: format!("Couldn't write `{}`", path.to_string_lossy()),
+                source,
+            }),
+        };
+    }
+    #[cfg(feature = "output-json")]
+    let replay = match args.from_plan {
+        Some(ref path) => Some(read_plan(path)?),
+        None => None,
+    };
+    #[cfg(not(feature = "output-json"))]
+    let replay: Option<CommitPlan> = None;
+    let dates = date_range(&args)?;
+    let per_day = match (&args.per_day_file, &args.from_git_log) {
+        (Some(path), _) => Some(PerDayPattern::load(
+            path,
+            platform_config(&args).week_start,
+        )?),
+        (None, Some(path)) => {
+            let source = match git2::Repository::open(path) {
+                Ok(ok) => ok,
+                Err(source) => {
+                    return Err(ContributronError::Git {
+                        context: format!(
+                            "Couldn't open `{}` to read its log",
+                            path.to_string_lossy()
+                        ),
+                        source,
+                    });
+                }
+            };
+            Some(PerDayPattern {
+                days: git_log_to_pattern(&source, *dates.start(), *dates.end(), args.scale)?,
+                week_start: platform_config(&args).week_start,
+            })
+        }
+        (None, None) => None,
+    };
+    // A replayed plan or per-day counts need no image (so these go unused):
+    let grid = if replay.is_some() || per_day.is_some() {
+        ContributionGrid::new(0, 7)
+    } else if args.animate {
+        animate_columns(&args, &dates)?
+    } else {
+        load_columns(&args, args.image.first().map(PathBuf::as_path))?
+    };
+    if let Some(ref path) = args.output_grid_png {
+        if replay.is_some() || per_day.is_some() {
+            return Err(ContributronError::InvalidArgCombination(
+                "`--output-grid-png` needs a pattern to draw, not a replayed plan or per-day counts"
+                    .to_owned(),
+            ));
+        }
+        match ContributionGrid::to_image(&grid).save(path) {
+            Ok(()) => {}
+            Err(source) => {
+                return Err(ContributronError::ImageSave {
+                    path: path.clone(),
+                    source,
+                });
+            }
+        }
+    }
+    if args.print_columns {
+        if replay.is_some() || per_day.is_some() {
+            return Err(ContributronError::InvalidArgCombination(
+                "`--print-columns` needs a pattern to draw, not a replayed plan or per-day counts"
+                    .to_owned(),
+            ));
+        }
+        let pixels = repeat_pattern(&grid, count_days(&dates)?);
+        print!(
+            "{}",
+            render_columns(&weeks(&pixels), platform_config(&args).week_start)
+        );
+        // Debugging only, so never commit:
+        return Ok(());
+    }
+    let cli_args = format!("{args:?}");
+    let week_start = platform_config(&args).week_start;
+    let image_name = match args.image.first().and_then(|image| image.file_name()) {
+        Some(name) => name.to_string_lossy().into_owned(),
+        None => String::new(),
+    };
+
+    let Args {
+        common:
+            CommonArgs {
+                repo,
+                ref name,
+                ref email,
+            },
+        image: _,
+        animate: _,
+        text: _,
+        letter_brightness: _,
+        spacing: _,
+        noise: _,
+        pattern: _,
+        pattern_period: _,
+        qr: _,
+        per_day_file: _,
+        from_git_log: _,
+        ref author_name,
+        ref author_email,
+        ref git_reference,
+        scale,
+        overwrite,
+        append,
+        update,
+        dither,
+        poisson,
+        resize: _,
+        resize_filter: _,
+        dry_run,
+        start_date: _,
+        since: _,
+        end_date: _,
+        allow_long_range: _,
+        year: _,
+        week_start: _,
+        platform: _,
+        timezone,
+        commit:
+            CommitArgs {
+                gpg_key_id,
+                gpg_program,
+                push: remote,
+                push_force,
+                tag_prefix,
+                tag_force,
+                jobs,
+                in_memory: objects_in_memory,
+            },
+        preview,
+        quiet,
+        config: _,
+        profile: _,
+        crop_to_content: _,
+        padding: _,
+        padding_left: _,
+        padding_right: _,
+        padding_rows_top: _,
+        padding_rows_bottom: _,
+        offset: _,
+        align: _,
+        repeat: _,
+        threshold: _,
+        curve: _,
+        posterize: _,
+        background: _,
+        weekday_only: _,
+        weekend_only: _,
+        weekend_boost: _,
+        invert: _,
+        brightness: _,
+        contrast: _,
+        blur: _,
+        grayscale_method: _,
+        alpha_threshold: _,
+        gamma: _,
+        auto_contrast: _,
+        flip_h: _,
+        flip_v: _,
+        rotate: _,
+        message,
+        message_file,
+        commit_time,
+        spread_commits,
+        spread_window,
+        gaussian_time,
+        jitter,
+        seed,
+        max_commits,
+        min_commits,
+        #[cfg(feature = "output-json")]
+        output_json,
+        output_csv,
+        output_svg,
+        output_ical,
+        output_preview,
+        output_grid_png: _,
+        confirm: confirm_first,
+        require_confirm,
+        yes,
+        verbose,
+        stats,
+        list_commit_counts,
+        print_columns: _,
+        content_file,
+        readme_template,
+        generate_workflow: _,
+        watch: _,
+        #[cfg(feature = "output-json")]
+            from_plan: _,
+        #[cfg(feature = "output-json")]
+        checkpoint,
+        #[cfg(feature = "output-json")]
+        resume,
+        #[cfg(feature = "output-json")]
+        force_resume,
+    } = args;
+    #[cfg(not(feature = "output-json"))]
+    let (checkpoint, resume): (Option<PathBuf>, bool) = (None, false);
+
+    // Catch a bad template now rather than at the first commit:
+    let messages = choose_messages(message, message_file.as_deref())?;
+
+    // Plan (and, in a dry run, "draw") without touching the disk at all:
+    let in_memory = {
+        let odb = match git2::Odb::new() {
+            Ok(ok) => ok,
+            Err(source) => {
+                return Err(ContributronError::Git {
+                    context: "Couldn't create an in-memory Git object database".to_owned(),
+                    source,
+                });
+            }
+        };
+        match git2::Repository::from_odb(odb) {
+            Ok(ok) => ok,
+            Err(source) => {
+                return Err(ContributronError::Git {
+                    context: "Couldn't create an in-memory Git repository".to_owned(),
+                    source,
+                });
+            }
+        }
+    };
+
+    let git = GitInfo {
+        repo: in_memory,
+        reference: git_reference,
+        name,
+        email,
+        author_name: author_name.as_deref().unwrap_or(name),
+        author_email: author_email.as_deref().unwrap_or(email),
+        dry_run,
+        offset_minutes: timezone,
+        gpg: gpg_key_id.map(|key_id| GpgSigner {
+            key_id,
+            program: gpg_program,
+        }),
+        quiet,
+        messages,
+        commits_made: Cell::new(0),
+        commit_time,
+        spread_hours: spread_commits.then_some(spread_window),
+        gaussian_minutes: gaussian_time,
+        jitter_secs: jitter,
+        rng: seed.map(|seed| RefCell::new(SmallRng::seed_from_u64(seed))),
+        min_commits,
+        max_commits,
+        poisson,
+        verbose,
+        content_file,
+        readme_template,
+        image_name,
+        checkpoint,
+    };
+    let mut commit_plan = match (replay, per_day) {
+        (Some(replay), _) => replay,
+        (None, Some(per_day)) => CommitPlan {
+            cli_args,
+            ..plan_per_day(&per_day, dates, &git)?
+        },
+        (None, None) => CommitPlan {
+            cli_args,
+            ..plan(&grid, dates, scale, dither, &git)?
+        },
+    };
+    // Appending picks up after the last commit already there (without shifting the image):
+    let existing = if append || update || resume {
+        Some(open_repo(&repo, git_reference)?)
+    } else {
+        None
+    };
+    if let Some(ref existing) = existing
+        && append
+        && let Some(last) = last_committed_date(existing, git_reference)?
+    {
+        let () = commit_plan.entries.retain(|entry| entry.date > last);
+    }
+    #[cfg(feature = "output-json")]
+    if let Some(ref existing) = existing
+        && resume
+        && let Some(ref path) = git.checkpoint
+    {
+        let checkpoint = read_checkpoint(path)?;
+        let head = existing
+            .find_reference(git_reference)
+            .and_then(|reference| reference.peel_to_commit())
+            .map(|commit| commit.id().to_string());
+        match head {
+            Ok(head) if head == checkpoint.head_oid => {}
+            Ok(_) | Err(_) if force_resume => {
+                eprintln!(
+                    "[warn] `{git_reference}` has moved since the checkpoint (at {}), but resuming anyway",
+                    checkpoint.head_oid,
+                );
+            }
+            Ok(head) => {
+                return Err(ContributronError::CheckpointDiverged(format!(
+                    "`{git_reference}` is on {head}, not the checkpoint's {}",
+                    checkpoint.head_oid,
+                )));
+            }
+            Err(e) => {
+                return Err(ContributronError::CheckpointDiverged(format!(
+                    "`{git_reference}` isn't on any commit ({e}), let alone the checkpoint's {}",
+                    checkpoint.head_oid,
+                )));
+            }
+        }
+        let () = commit_plan
+            .entries
+            .retain(|entry| entry.date > checkpoint.last_completed_date);
+    }
+    // Updating tops up each day to what it should have:
+    if let Some(ref existing) = existing
+        && update
+    {
+        let counts = count_commits_per_day(existing, git_reference)?;
+        let (mut added, mut unchanged) = (0_usize, 0_usize);
+        for entry in &mut commit_plan.entries {
+            let already = counts.get(&entry.date).copied().unwrap_or(0);
+            let already = u8::try_from(already)
+                .unwrap_or(u8::MAX)
+                .min(entry.commit_count);
+            if already == entry.commit_count {
+                if already > 0 {
+                    unchanged += 1;
+                }
+            } else {
+                added += 1;
+            }
+            entry.commit_count -= already;
+            entry.messages = entry.messages.split_off(usize::from(already));
+            if entry.times.len() >= usize::from(already) {
+                entry.times = entry.times.split_off(usize::from(already));
+            }
+        }
+        if !git.quiet {
+            eprintln!("Updating: adding commits on {added} days, leaving {unchanged} unchanged");
+        }
+    }
+    if preview {
+        if !git.quiet {
+            print!(
+                "{}",
+                preview_grid(&plan_grid(&commit_plan, week_start), scale)
+            );
+        }
+        if dry_run {
+            return Ok(());
+        }
+    }
+    if stats || git.verbose {
+        eprintln!("[stats] {}", statistics(&commit_plan));
+    }
+    if list_commit_counts && !git.quiet {
+        println!("{}", commit_count_table(&commit_plan));
+    }
+    if let Some(path) = output_csv {
+        let () = write_plan_csv(&commit_plan, &path)?;
+    }
+    if let Some(path) = output_svg {
+        match fs::write(&path, render_svg(&commit_plan, week_start)) {
+            Ok(()) => {}
+            Err(source) => {
+                return Err(ContributronError::Io {
+                    context: format!("Couldn't write `{}`", path.to_string_lossy()),
+                    source,
+                });
+            }
+        }
+    }
+    if let Some(path) = output_ical {
+        match fs::write(&path, render_ical(&commit_plan)) {
+            Ok(()) => {}
+            Err(source) => {
+                return Err(ContributronError::Io {
+                    context: format!("Couldn't write `{}`", path.to_string_lossy()),
+                    source,
+                });
+            }
+        }
+    }
+    if let Some(path) = output_preview {
+        match render_preview_png(&commit_plan, week_start).save(&path) {
+            Ok(()) => {}
+            Err(source) => {
+                return Err(ContributronError::ImageSave { path, source });
+            }
+        }
+    }
+    #[cfg(feature = "output-json")]
+    if let Some(path) = output_json {
+        let () = write_plan(&commit_plan, &path)?;
+        // The JSON replaces the dry run's usual output:
+        if git.dry_run {
+            return Ok(());
+        }
+    }
+
+    if !dry_run {
+        let () = confirm(&commit_plan, &repo, yes, require_confirm, confirm_first)?;
+    }
+    let git = if dry_run {
+        git
+    } else if let Some(existing) = existing {
+        GitInfo {
+            repo: existing,
+            ..git
+        }
+    } else {
+        GitInfo {
+            repo: init_repo(&repo, overwrite)?,
+            ..git
+        }
+    };
+
+    #[cfg(feature = "progress")]
+    let progress = if git.quiet || git.dry_run {
+        indicatif::ProgressBar::hidden()
+    } else {
+        let progress = indicatif::ProgressBar::new(commit_plan.entries.len() as u64);
+        let () = progress.set_style(
+            match indicatif::ProgressStyle::with_template(
+                "[{elapsed_precise}] {wide_bar} {percent:>3}% ({msg}, ETA {eta})",
+            ) {
+                Ok(ok) => ok,
+                Err(e) => {
+                    return Err(ContributronError::InvalidArgument(format!(
+                        "Internal error: invalid progress bar template: {e}"
+                    )));
+                }
+            },
+        );
+        progress
+    };
+    let odb = match git.repo.odb() {
+        Ok(ok) => ok,
+        Err(source) => {
+            return Err(ContributronError::Git {
+                context: "Couldn't open the repo's object database".to_owned(),
+                source,
+            });
+        }
+    };
+    // Take priority over the loose-object (and pack) backends, so that every new object lands here:
+    let mempack = if objects_in_memory && !git.dry_run {
+        match odb.add_new_mempack_backend(1000) {
+            Ok(ok) => Some(ok),
+            Err(source) => {
+                return Err(ContributronError::Git {
+                    context: "Couldn't keep Git objects in memory".to_owned(),
+                    source,
+                });
+            }
+        }
+    } else {
+        None
+    };
+    let before = head_commit(&git);
+    let drawn = draw_and_record(
+        &git,
+        &commit_plan,
+        usize::from(jobs),
+        before,
+        tag_prefix.as_deref().map(|prefix| (prefix, tag_force)),
+        #[cfg(feature = "progress")]
+        &progress,
+    );
+    // However far that got, the references it moved point into the mempack, so write it out first:
+    if let Some(ref mempack) = mempack {
+        let () = write_pack(&git.repo, &odb, mempack)?;
+    }
+    let () = drawn?;
+
+    if let Some(remote) = remote
+        && !git.dry_run
+    {
+        let () = push(&git.repo, git.reference, &remote, push_force)?;
+    }
+
+    Ok(())
+}
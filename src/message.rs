@@ -0,0 +1,151 @@
+//! Commit messages (and other text that changes commit by commit).
+
+use {
+    crate::ContributronError,
+    chrono::{DateTime, FixedOffset, NaiveDate},
+    std::{fs, path::Path},
+};
+
+/// The original commit message: which commit this is out of the day's, then the pixel's value.
+pub const DEFAULT_MESSAGE: &str = "#{index}/{pixel}";
+
+/// Read a file of commit-message templates, one per line, skipping blank lines.
+/// # Errors
+/// If the file can't be read (or isn't UTF-8), has no non-blank lines,
+/// or has an invalid template (see `format_message`).
+#[inline]
+pub fn load_messages(path: &Path) -> Result<Vec<String>, ContributronError> {
+    let contents = match fs::read_to_string(path) {
+        Ok(ok) => ok,
+        Err(source) => {
+            return Err(ContributronError::Io {
+                context: format!("Couldn't read `{}`", path.to_string_lossy()),
+                source,
+            });
+        }
+    };
+    let messages: Vec<String> = contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(str::to_owned)
+        .collect();
+    if messages.is_empty() {
+        return Err(ContributronError::InvalidArgument(format!(
+            "`{}` has no commit messages (only blank lines)",
+            path.to_string_lossy(),
+        )));
+    }
+    for template in &messages {
+        let _: String = format_message(
+            template,
+            1,
+            1,
+            0,
+            NaiveDate::MIN,
+            DateTime::UNIX_EPOCH.fixed_offset(),
+        )?;
+    }
+    Ok(messages)
+}
+
+/// The commit-message templates to cycle through:
+/// either those in `message_file`, or `message`, or `DEFAULT_MESSAGE`.
+/// # Errors
+/// If both are given, or see `load_messages` and `format_message`.
+#[inline]
+pub fn choose_messages(
+    message: Option<String>,
+    message_file: Option<&Path>,
+) -> Result<Vec<String>, ContributronError> {
+    match (message, message_file) {
+        (Some(_), Some(_)) => Err(ContributronError::ConflictingArgs(
+            "--message and --message-file are mutually exclusive".to_owned(),
+        )),
+        (None, Some(path)) => load_messages(path),
+        (message, None) => {
+            let message = message.unwrap_or_else(|| DEFAULT_MESSAGE.to_owned());
+            let _: String = format_message(
+                &message,
+                1,
+                1,
+                0,
+                NaiveDate::MIN,
+                DateTime::UNIX_EPOCH.fixed_offset(),
+            )?;
+            Ok(vec![message])
+        }
+    }
+}
+
+/// Fill in a commit-message template. Placeholders are
+/// `{index}` (this commit's number within its day, starting at 1),
+/// `{total}` (the number of commits that day),
+/// `{date}` (the day being drawn, e.g. `2024-03-10`),
+/// `{iso8601}` (`time`, the commit's author time, e.g. `2024-03-10T12:00:00+00:00`),
+/// and `{pixel}` (the pixel's value, `0..=255`);
+/// write `{{` or `}}` for a literal brace.
+/// # Errors
+/// If the template has an unknown placeholder or an unmatched brace.
+#[inline]
+pub fn format_message(
+    template: &str,
+    index: u8,
+    total: u8,
+    pixel: u8,
+    date: NaiveDate,
+    time: DateTime<FixedOffset>,
+) -> Result<String, ContributronError> {
+    let mut message = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(brace) = rest.find(['{', '}']) {
+        let () = message.push_str(&rest[..brace]);
+        let from_brace = &rest[brace..];
+        if from_brace.starts_with("{{") || from_brace.starts_with("}}") {
+            let () = message.push_str(&from_brace[..1]);
+            rest = &from_brace[2..];
+            continue;
+        }
+        let (Some(after_open), Some(close)) = (from_brace.strip_prefix('{'), from_brace.find('}'))
+        else {
+            return Err(ContributronError::InvalidMessageTemplate(format!(
+                "unmatched brace in `{template}` (write `{{{{` or `}}}}` for a literal brace)"
+            )));
+        };
+        let () = match &after_open[..close - 1] {
+            "index" => message.push_str(&index.to_string()),
+            "total" => message.push_str(&total.to_string()),
+            "date" => message.push_str(&date.to_string()),
+            "iso8601" => message.push_str(&time.to_rfc3339()),
+            "pixel" => message.push_str(&pixel.to_string()),
+            unknown => {
+                return Err(ContributronError::InvalidMessageTemplate(format!(
+                    "unknown placeholder `{{{unknown}}}` in `{template}` (expected `{{index}}`, `{{total}}`, `{{date}}`, `{{iso8601}}`, or `{{pixel}}`)"
+                )));
+            }
+        };
+        rest = &from_brace[close + 1..];
+    }
+    let () = message.push_str(rest);
+    Ok(message)
+}
+
+/// Fill in a `README.md` template. Placeholders are
+/// `{date}` (e.g. `2024-03-10`),
+/// `{commit_index}` (this commit's number within its day, starting at 1),
+/// `{image_name}` (the drawn image's file name, or empty without `--image`),
+/// and `{name}` (the author's); anything else is left as is.
+#[inline]
+#[must_use]
+pub fn render_readme(
+    template: &str,
+    date: NaiveDate,
+    index: u8,
+    image_name: &str,
+    name: &str,
+) -> String {
+    template
+        .replace("{date}", &date.to_string())
+        .replace("{commit_index}", &index.to_string())
+        .replace("{image_name}", image_name)
+        .replace("{name}", name)
+}
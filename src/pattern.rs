@@ -0,0 +1,292 @@
+//! Sources of columns to draw, other than a preprocessed image.
+
+use {
+    crate::{ContributionGrid, ContributronError, PatternSource, WeekStart, image_to_columns},
+    chrono::NaiveDate,
+    image::{DynamicImage, GrayImage, Luma, imageops::FilterType},
+    noise::{NoiseFn as _, Perlin},
+    rand::{Rng as _, SeedableRng as _, rngs::SmallRng},
+    std::{collections::HashMap, path::Path},
+};
+
+/// An image (assumed to be seven pixels tall), drawn as is.
+#[derive(Clone, Debug)]
+pub struct ImagePattern(pub DynamicImage);
+
+impl PatternSource for ImagePattern {
+    #[inline]
+    fn columns(&self) -> ContributionGrid {
+        image_to_columns(&self.0)
+    }
+}
+
+/// A simple geometric pattern, generated on the fly.
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum, serde::Deserialize, serde::Serialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum BuiltinKind {
+    /// Alternating squares.
+    Checkerboard,
+    /// Alternating rows (i.e. days of the week).
+    StripesH,
+    /// Alternating columns (i.e. weeks).
+    StripesV,
+    /// Alternating diagonal stripes (the same as a checkerboard with a period of 1).
+    Diagonal,
+    /// Every cell.
+    Solid,
+    /// Every cell with probability 1/2, reproducibly for each seed.
+    Random,
+}
+
+/// A built-in pattern as wide as the contribution graph (see `builtin_pattern`).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BuiltinPattern {
+    /// Which pattern.
+    pub kind: BuiltinKind,
+    /// Luma of each lit cell.
+    pub brightness: u8,
+    /// Width of each square or stripe, in cells.
+    pub period: u8,
+    /// Seed for `BuiltinKind::Random`.
+    pub seed: u64,
+    /// Width of the contribution graph (see `PlatformConfig::weeks`).
+    pub weeks: u16,
+}
+
+impl PatternSource for BuiltinPattern {
+    #[inline]
+    fn columns(&self) -> ContributionGrid {
+        let Self {
+            kind,
+            brightness,
+            period,
+            seed,
+            weeks,
+        } = *self;
+        builtin_pattern(kind, brightness, period, seed, weeks)
+    }
+}
+
+/// Generate a built-in pattern as wide as the contribution graph (`weeks` columns),
+/// lighting cells at `brightness`, with squares or stripes `period` cells wide
+/// (`seed` matters only for `BuiltinKind::Random`).
+#[inline]
+#[must_use]
+pub fn builtin_pattern(
+    kind: BuiltinKind,
+    brightness: u8,
+    period: u8,
+    seed: u64,
+    weeks: u16,
+) -> ContributionGrid {
+    let period = usize::from(period.max(1));
+    let mut rng = SmallRng::seed_from_u64(seed);
+    let mut grid = ContributionGrid::new(usize::from(weeks), 7);
+    for (x, column) in grid.cells.iter_mut().enumerate() {
+        for (y, pixel) in column.iter_mut().enumerate() {
+            let lit = match kind {
+                BuiltinKind::Checkerboard => (x / period + y / period) % 2 == 0,
+                BuiltinKind::StripesH => (y / period) % 2 == 0,
+                BuiltinKind::StripesV => (x / period) % 2 == 0,
+                BuiltinKind::Diagonal => ((x + y) / period) % 2 == 0,
+                BuiltinKind::Solid => true,
+                BuiltinKind::Random => rng.gen_bool(0.5),
+            };
+            if lit {
+                *pixel = brightness;
+            }
+        }
+    }
+    grid
+}
+
+/// Perlin noise as wide as the contribution graph: organic blobs, reproducibly for each `seed`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NoisePattern {
+    /// Sampling frequency, in noise cycles per cell:
+    /// low values (e.g. 0.1) make large, smooth blobs; high values (e.g. 2) make fine grain.
+    pub density: f64,
+    /// Seed for the noise.
+    pub seed: u64,
+    /// Width of the contribution graph (see `PlatformConfig::weeks`).
+    pub weeks: u16,
+}
+
+impl PatternSource for NoisePattern {
+    #[inline]
+    fn columns(&self) -> ContributionGrid {
+        // `Perlin` takes only 32 bits, so fold in the upper half rather than drop it:
+        let perlin = Perlin::new((self.seed ^ (self.seed >> 32)) as u32);
+        let mut grid = ContributionGrid::new(usize::from(self.weeks), 7);
+        for (x, column) in (0_u16..).zip(&mut grid.cells) {
+            for (y, pixel) in (0_u8..).zip(column) {
+                // Every integer point is zero, so sample between them:
+                let value = perlin.get([
+                    f64::from(x).mul_add(self.density, 0.5),
+                    f64::from(y).mul_add(self.density, 0.5),
+                ]);
+                *pixel = ((value.clamp(-1., 1.) + 1.) / 2. * f64::from(u8::MAX)).round() as u8;
+            }
+        }
+        grid
+    }
+}
+
+/// Encode `content` as a QR code (of the smallest version that fits)
+/// and shrink it to seven modules square (each the majority of the modules it replaces),
+/// with dark modules fully lit and light ones black.
+/// Seven rows are far fewer than even the smallest QR code's 21,
+/// so the result has a QR code's texture but won't scan.
+/// # Errors
+/// If `content` can't be encoded in a QR code at most as wide (in modules) as the contribution graph (`weeks`).
+#[inline]
+pub fn qr_to_columns(content: &str, weeks: u16) -> Result<ContributionGrid, ContributronError> {
+    let code = match qrcode::QrCode::new(content) {
+        Ok(ok) => ok,
+        Err(e) => return Err(ContributronError::QrEncodingFailed(e.to_string())),
+    };
+    let width = code.width();
+    if width > usize::from(weeks) {
+        return Err(ContributronError::QrEncodingFailed(format!(
+            "`{content}` needs a QR code {width} modules wide, but the contribution graph is only {weeks} columns wide"
+        )));
+    }
+    let colors = code.to_colors();
+    let modules = GrayImage::from_fn(width as u32, width as u32, |x, y| {
+        if colors[y as usize * width + x as usize] == qrcode::Color::Dark {
+            Luma([u8::MAX])
+        } else {
+            Luma([0])
+        }
+    });
+    // Average each block of modules, then round it back to dark or light:
+    let shrunk = DynamicImage::ImageLuma8(modules).resize_exact(7, 7, FilterType::Triangle);
+    let mut columns = image_to_columns(&shrunk);
+    for pixel in columns.cells.iter_mut().flatten() {
+        *pixel = if *pixel >= 128 { u8::MAX } else { 0 };
+    }
+    Ok(columns)
+}
+
+/// Exactly how many commits to make on each of these days (and none on any other).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PerDayPattern {
+    /// Number of commits on each day listed.
+    pub days: HashMap<NaiveDate, u8>,
+    /// Which day of the week starts each column (see `PatternSource::columns`).
+    pub week_start: WeekStart,
+}
+
+/// One row of a `--per-day-file`.
+#[derive(serde::Deserialize)]
+struct PerDayRow {
+    /// Day to commit on.
+    date: NaiveDate,
+    /// Number of commits to make that day.
+    count: u8,
+}
+
+impl PerDayPattern {
+    /// Read a CSV file with columns `date` (e.g. `2024-03-10`) and `count`,
+    /// to be laid out in weeks starting on `week_start`.
+    /// # Errors
+    /// If the file can't be read or any row isn't a date and a count from 0 to 255.
+    #[inline]
+    pub fn load(path: &Path, week_start: WeekStart) -> Result<Self, ContributronError> {
+        let mut reader = match csv::Reader::from_path(path) {
+            Ok(ok) => ok,
+            Err(source) => {
+                return Err(ContributronError::Csv {
+                    context: format!("Couldn't read `{}`", path.to_string_lossy()),
+                    source,
+                });
+            }
+        };
+        let mut counts = HashMap::new();
+        for row in reader.deserialize() {
+            let PerDayRow { date, count } = match row {
+                Ok(ok) => ok,
+                Err(source) => {
+                    return Err(ContributronError::Csv {
+                        context: format!(
+                            "`{}` should have columns `date,count`",
+                            path.to_string_lossy()
+                        ),
+                        source,
+                    });
+                }
+            };
+            let _ = counts.insert(date, count);
+        }
+        Ok(Self {
+            days: counts,
+            week_start,
+        })
+    }
+
+    /// Number of commits on `date` (0 if it's not listed).
+    #[inline]
+    #[must_use]
+    pub fn count(&self, date: NaiveDate) -> u8 {
+        self.days.get(&date).copied().unwrap_or(0)
+    }
+
+    /// Number of commits on the busiest day listed (0 if none are).
+    #[inline]
+    #[must_use]
+    pub fn busiest(&self) -> u8 {
+        self.days.values().copied().max().unwrap_or(0)
+    }
+
+    /// Luma of `date`, relative to the busiest day (which is white, and has `busiest` commits; see `busiest`).
+    #[inline]
+    #[must_use]
+    pub fn brightness(&self, date: NaiveDate, busiest: u8) -> u8 {
+        if busiest == 0 {
+            return 0;
+        }
+        (u16::from(self.count(date)) * u16::from(u8::MAX) / u16::from(busiest)) as u8
+    }
+}
+
+impl PatternSource for PerDayPattern {
+    /// Every week (starting on `week_start`) from the one with the first day listed to the one with the last.
+    #[inline]
+    fn columns(&self) -> ContributionGrid {
+        let (Some(&first), Some(&last)) = (self.days.keys().min(), self.days.keys().max()) else {
+            return ContributionGrid::new(0, 7);
+        };
+        let week = self.week_start.week_of(first).unwrap_or(first);
+        let busiest = self.busiest();
+        let days: Vec<u8> = week
+            .iter_days()
+            .take_while(|&date| date <= last)
+            .map(|date| self.brightness(date, busiest))
+            .collect();
+        ContributionGrid::from_columns(days.chunks(7).map(<[u8]>::to_vec).collect(), 7)
+    }
+}
+
+impl<const N: usize> PatternSource for [[u8; 7]; N] {
+    #[inline]
+    fn columns(&self) -> ContributionGrid {
+        ContributionGrid::from(self.to_vec())
+    }
+}
+
+impl PatternSource for ContributionGrid {
+    /// Every row of every column (though only the first seven of each are drawn; see `plan`).
+    #[inline]
+    fn columns(&self) -> ContributionGrid {
+        self.clone()
+    }
+}
+
+impl PatternSource for Vec<[u8; 7]> {
+    #[inline]
+    fn columns(&self) -> ContributionGrid {
+        ContributionGrid::from(self.clone())
+    }
+}
@@ -0,0 +1,403 @@
+//! Every commit to be made, worked out in advance without touching Git.
+
+use {
+    crate::{
+        ContributionGrid, ContributronError, DitherMode, GitInfo, PerDayPattern, commit_counts,
+        count_days, limit_commits, plan_day, poisson_counts, repeat_pattern,
+    },
+    chrono::{DateTime, Days, FixedOffset, NaiveDate, Utc},
+    core::fmt,
+    std::{
+        collections::BTreeMap,
+        fs,
+        io::{self, Write},
+        iter,
+        ops::RangeInclusive,
+        path::Path,
+    },
+};
+
+/// The whole schedule of commits for one drawing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct CommitPlan {
+    /// Version of contributron that made this plan.
+    pub version: String,
+    /// One entry per day, in order (including days without any commits).
+    pub entries: Vec<CommitEntry>,
+    /// When this plan was made.
+    pub generated_at: DateTime<Utc>,
+    /// The arguments that produced this plan, if known (`plan` leaves this empty).
+    pub cli_args: String,
+}
+
+/// The commits planned for one day.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct CommitEntry {
+    /// The day.
+    pub date: NaiveDate,
+    /// Its pixel, after all preprocessing.
+    pub pixel: u8,
+    /// How many commits to make on it.
+    pub commit_count: u8,
+    /// The message of each commit, in order.
+    pub messages: Vec<String>,
+    /// The author time of each commit, in order
+    /// (empty in plans saved before these were recorded, so they're worked out when drawn).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub times: Vec<DateTime<FixedOffset>>,
+}
+
+/// Like `plan`, but making exactly as many commits as `days` says on each day
+/// (still within `--min-commits` and `--max-commits`), warning about any day listed outside `dates`.
+/// # Errors
+/// If `dates` is backwards or any commit message can't be formatted.
+#[inline]
+pub fn plan_per_day(
+    days: &PerDayPattern,
+    dates: RangeInclusive<NaiveDate>,
+    git: &GitInfo,
+) -> Result<CommitPlan, ContributronError> {
+    let mut outside: Vec<NaiveDate> = days
+        .days
+        .keys()
+        .copied()
+        .filter(|date| !dates.contains(date))
+        .collect();
+    if !outside.is_empty() {
+        let () = outside.sort_unstable();
+        eprintln!(
+            "[warn] `--per-day-file` lists {} day(s) outside {} to {}, which won't be drawn: {}",
+            outside.len(),
+            dates.start(),
+            dates.end(),
+            outside
+                .iter()
+                .map(NaiveDate::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+        );
+    }
+
+    let busiest = days.busiest();
+    let mut commits_made = git.commits_made.get();
+    let mut entries = Vec::with_capacity(count_days(&dates)?);
+    for date in dates
+        .start()
+        .iter_days()
+        .take_while(|date| date <= dates.end())
+    {
+        let pixel = days.brightness(date, busiest);
+        let n_commits = limit_commits(pixel, days.count(date), git.min_commits, git.max_commits);
+        let () = entries.push(plan_day(git, commits_made, pixel, n_commits, date)?);
+        commits_made += usize::from(n_commits);
+    }
+
+    Ok(CommitPlan {
+        version: env!("CARGO_PKG_VERSION").to_owned(),
+        entries,
+        generated_at: Utc::now(),
+        cli_args: String::new(),
+    })
+}
+
+/// A summary of a plan's commits per day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GridStats {
+    /// Commits across every day.
+    pub total_commits: u64,
+    /// Days with any commits.
+    pub active_days: usize,
+    /// Days in the plan, with or without commits.
+    pub total_days: usize,
+    /// Commits on the busiest day.
+    pub max_commits_per_day: u8,
+    /// Commits on the quietest day with any (or 0, if there are none).
+    pub min_nonzero_commits_per_day: u8,
+    /// Average commits on each day with any (or 0, if there are none).
+    pub mean_commits_per_active_day: f32,
+    /// Number of days with each number of commits (e.g. `histogram[0]` days with none).
+    pub histogram: [u32; 256],
+}
+
+impl fmt::Display for GridStats {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            total_commits,
+            active_days,
+            total_days,
+            max_commits_per_day,
+            min_nonzero_commits_per_day,
+            mean_commits_per_active_day,
+            histogram: _,
+        } = *self;
+        write!(
+            f,
+            "{total_commits} commits on {active_days} of {total_days} days \
+             ({min_nonzero_commits_per_day} to {max_commits_per_day} per active day, \
+             {mean_commits_per_active_day:.1} on average)",
+        )
+    }
+}
+
+/// Summarize how many commits `plan` makes on each day.
+#[inline]
+#[must_use]
+pub fn statistics(plan: &CommitPlan) -> GridStats {
+    let mut histogram = [0; 256];
+    for entry in &plan.entries {
+        histogram[usize::from(entry.commit_count)] += 1;
+    }
+    let active = || {
+        plan.entries
+            .iter()
+            .map(|entry| entry.commit_count)
+            .filter(|&count| count > 0)
+    };
+    let total_commits: u64 = active().map(u64::from).sum();
+    let active_days = active().count();
+    GridStats {
+        total_commits,
+        active_days,
+        total_days: plan.entries.len(),
+        max_commits_per_day: active().max().unwrap_or(0),
+        min_nonzero_commits_per_day: active().min().unwrap_or(0),
+        mean_commits_per_active_day: if active_days == 0 {
+            0.
+        } else {
+            total_commits as f32 / active_days as f32
+        },
+        histogram,
+    }
+}
+
+/// How many days of `plan` get each number of commits, fewest commits first
+/// (e.g. `0 commits: 210 days, 1 commit: 45 days, 3 commits: 12 days`).
+#[inline]
+#[must_use]
+pub fn commit_count_table(plan: &CommitPlan) -> String {
+    let mut days = BTreeMap::<u8, u32>::new();
+    for entry in &plan.entries {
+        *days.entry(entry.commit_count).or_default() += 1;
+    }
+    days.into_iter()
+        .map(|(count, n_days)| {
+            let commits = if count == 1 { "commit" } else { "commits" };
+            let days = if n_days == 1 { "day" } else { "days" };
+            format!("{count} {commits}: {n_days} {days}")
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// How far a run got, as written after each day by `--checkpoint`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Checkpoint {
+    /// The last day whose commits were all made.
+    pub last_completed_date: NaiveDate,
+    /// The commit that the reference was on right after them.
+    pub head_oid: String,
+}
+
+/// Work out exactly which commits `draw_repeating_pattern` would make, without making them.
+/// # Errors
+/// If `dates` is backwards or a message template is invalid.
+#[inline]
+pub fn plan(
+    grid: &ContributionGrid,
+    dates: RangeInclusive<NaiveDate>,
+    scale: u8,
+    dither: Option<DitherMode>,
+    git: &GitInfo,
+) -> Result<CommitPlan, ContributronError> {
+    let pixels = repeat_pattern(grid, count_days(&dates)?);
+    let counts = match (git.poisson, &git.rng) {
+        (false, _) => commit_counts(&pixels, scale, dither),
+        (true, Some(rng)) => poisson_counts(&pixels, scale, &mut *rng.borrow_mut()),
+        (true, None) => poisson_counts(&pixels, scale, &mut rand::thread_rng()),
+    };
+
+    let mut commits_made = git.commits_made.get();
+    let mut entries = Vec::with_capacity(pixels.len());
+    let mut date = *dates.start();
+    for (pixel, n_commits) in pixels.iter().copied().zip(counts) {
+        let n_commits = limit_commits(pixel, n_commits, git.min_commits, git.max_commits);
+        let () = entries.push(plan_day(git, commits_made, pixel, n_commits, date)?);
+        commits_made += usize::from(n_commits);
+        date = match date.checked_add_days(Days::new(1)) {
+            Some(some) => some,
+            None => {
+                return Err(ContributronError::InvalidDateArithmetic(format!(
+                    "couldn't add 1 day to {date}"
+                )));
+            }
+        };
+    }
+
+    Ok(CommitPlan {
+        version: env!("CARGO_PKG_VERSION").to_owned(),
+        entries,
+        generated_at: Utc::now(),
+        cli_args: String::new(),
+    })
+}
+
+/// Write `plan` as JSON to `path` (or, if `path` is `-`, to standard output).
+/// # Errors
+/// If the file can't be written.
+#[cfg(feature = "output-json")]
+#[inline]
+pub fn write_plan(plan: &CommitPlan, path: &Path) -> Result<(), ContributronError> {
+    let json = match serde_json::to_string_pretty(plan) {
+        Ok(ok) => ok,
+        Err(source) => {
+            return Err(ContributronError::Json {
+                context: "Couldn't serialize the plan".to_owned(),
+                source,
+            });
+        }
+    };
+    let written = if path.as_os_str() == "-" {
+        writeln!(std::io::stdout(), "{json}")
+    } else {
+        fs::write(path, json + "\n")
+    };
+    match written {
+        Ok(()) => Ok(()),
+        Err(source) => Err(ContributronError::Io {
+            context: format!("Couldn't write the plan to `{}`", path.to_string_lossy()),
+            source,
+        }),
+    }
+}
+
+/// Read a plan written by `write_plan`.
+/// # Errors
+/// If the file can't be read or isn't a valid plan.
+#[cfg(feature = "output-json")]
+#[inline]
+pub fn read_plan(path: &Path) -> Result<CommitPlan, ContributronError> {
+    let json = match fs::read_to_string(path) {
+        Ok(ok) => ok,
+        Err(source) => {
+            return Err(ContributronError::Io {
+                context: format!("Couldn't read `{}`", path.to_string_lossy()),
+                source,
+            });
+        }
+    };
+    match serde_json::from_str(&json) {
+        Ok(ok) => Ok(ok),
+        Err(source) => Err(ContributronError::Json {
+            context: format!("`{}` isn't a valid plan", path.to_string_lossy()),
+            source,
+        }),
+    }
+}
+
+/// Write `checkpoint` as JSON to `path`, replacing any earlier one.
+/// # Errors
+/// If the file can't be written.
+#[cfg(feature = "output-json")]
+#[inline]
+pub fn write_checkpoint(path: &Path, checkpoint: &Checkpoint) -> Result<(), ContributronError> {
+    let json = match serde_json::to_string(checkpoint) {
+        Ok(ok) => ok,
+        Err(source) => {
+            return Err(ContributronError::Json {
+                context: "Couldn't serialize the checkpoint".to_owned(),
+                source,
+            });
+        }
+    };
+    match fs::write(path, json + "\n") {
+        Ok(()) => Ok(()),
+        Err(source) => Err(ContributronError::Io {
+            context: format!(
+                "Couldn't write the checkpoint to `{}`",
+                path.to_string_lossy()
+            ),
+            source,
+        }),
+    }
+}
+
+/// Read a checkpoint written by `write_checkpoint`.
+/// # Errors
+/// If the file can't be read or isn't a valid checkpoint.
+#[cfg(feature = "output-json")]
+#[inline]
+pub fn read_checkpoint(path: &Path) -> Result<Checkpoint, ContributronError> {
+    let json = match fs::read_to_string(path) {
+        Ok(ok) => ok,
+        Err(source) => {
+            return Err(ContributronError::Io {
+                context: format!("Couldn't read `{}`", path.to_string_lossy()),
+                source,
+            });
+        }
+    };
+    match serde_json::from_str(&json) {
+        Ok(ok) => Ok(ok),
+        Err(source) => Err(ContributronError::Json {
+            context: format!("`{}` isn't a valid checkpoint", path.to_string_lossy()),
+            source,
+        }),
+    }
+}
+
+/// Write one CSV row per planned commit (`date,commit_index,total_for_day,message`, after a header)
+/// to `path` (or, if `path` is `-`, to standard output).
+/// # Errors
+/// If the file can't be written.
+#[inline]
+pub fn write_plan_csv(plan: &CommitPlan, path: &Path) -> Result<(), ContributronError> {
+    let writer: Box<dyn Write> = if path.as_os_str() == "-" {
+        Box::new(io::stdout())
+    } else {
+        match fs::File::create(path) {
+            Ok(ok) => Box::new(ok),
+            Err(source) => {
+                return Err(ContributronError::Io {
+                    context: format!("Couldn't create `{}`", path.to_string_lossy()),
+                    source,
+                });
+            }
+        }
+    };
+    let mut writer = csv::Writer::from_writer(writer);
+    let header = ["date", "commit_index", "total_for_day", "message"].map(str::to_owned);
+    let rows = plan.entries.iter().flat_map(|entry| {
+        let date = entry.date.format("%Y-%m-%d").to_string();
+        let total = entry.commit_count.to_string();
+        (1_u16..).zip(&entry.messages).map(move |(index, message)| {
+            [
+                date.clone(),
+                index.to_string(),
+                total.clone(),
+                message.clone(),
+            ]
+        })
+    });
+    for row in iter::once(header).chain(rows) {
+        match writer.write_record(&row) {
+            Ok(()) => {}
+            Err(source) => {
+                return Err(ContributronError::Csv {
+                    context: format!("Couldn't write the plan to `{}`", path.to_string_lossy()),
+                    source,
+                });
+            }
+        }
+    }
+    match writer.flush() {
+        Ok(()) => Ok(()),
+        Err(source) => Err(ContributronError::Io {
+            context: format!("Couldn't write the plan to `{}`", path.to_string_lossy()),
+            source,
+        }),
+    }
+}
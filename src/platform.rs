@@ -0,0 +1,169 @@
+//! Where the contribution graph will be shown, and how that changes its layout.
+
+use {
+    crate::ContributronError,
+    chrono::{Datelike as _, Days, NaiveDate, Weekday},
+};
+
+/// Which day of the week is the top row of the contribution graph.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    clap::ValueEnum,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum WeekStart {
+    /// Sunday on top (like GitHub).
+    #[default]
+    Sunday,
+    /// Monday on top (like GitLab).
+    Monday,
+}
+
+impl WeekStart {
+    /// The row (from 0 at the top) that `weekday` is drawn on.
+    #[inline]
+    #[must_use]
+    pub const fn row(self, weekday: Weekday) -> u32 {
+        match self {
+            Self::Sunday => weekday.num_days_from_sunday(),
+            Self::Monday => weekday.num_days_from_monday(),
+        }
+    }
+
+    /// The rows that Saturday and Sunday are drawn on.
+    #[inline]
+    #[must_use]
+    pub const fn weekend_rows(self) -> [usize; 2] {
+        [
+            self.row(Weekday::Sat) as usize,
+            self.row(Weekday::Sun) as usize,
+        ]
+    }
+
+    /// The first day of the week that `date` is in.
+    /// # Errors
+    /// If that's before the earliest representable date.
+    #[inline]
+    pub fn week_of(self, date: NaiveDate) -> Result<NaiveDate, ContributronError> {
+        let days_since_start = self.row(date.weekday());
+        match date.checked_sub_days(Days::new(days_since_start.into())) {
+            Some(some) => Ok(some),
+            None => Err(ContributronError::InvalidDateArithmetic(format!(
+                "couldn't subtract {days_since_start} days from {date}"
+            ))),
+        }
+    }
+}
+
+/// How a platform lays out its contribution graph.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct PlatformConfig {
+    /// Number of columns (weeks) in the graph.
+    pub weeks: u16,
+    /// Which day of the week is the top row (and starts each column).
+    pub week_start: WeekStart,
+}
+
+impl PlatformConfig {
+    /// Number of days in the graph.
+    #[inline]
+    #[must_use]
+    pub const fn days(self) -> u16 {
+        7 * self.weeks
+    }
+}
+
+/// A Git hosting service whose contribution graph to draw on.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    clap::ValueEnum,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Platform {
+    /// GitHub (Sunday on top).
+    #[default]
+    #[value(name = "github")]
+    GitHub,
+    /// GitLab (Monday on top).
+    #[value(name = "gitlab")]
+    GitLab,
+    /// Gitea and Forgejo (Sunday on top, and a week narrower).
+    #[value(name = "gitea")]
+    Gitea,
+}
+
+impl Platform {
+    /// Every platform, in the order `list-platforms` prints them.
+    pub const ALL: [Self; 3] = [Self::GitHub, Self::GitLab, Self::Gitea];
+
+    /// How this platform lays out its graph
+    /// (each about a year of whole weeks, ending with the current one).
+    #[inline]
+    #[must_use]
+    pub const fn config(self) -> PlatformConfig {
+        match self {
+            Self::GitHub => PlatformConfig {
+                weeks: 53,
+                week_start: WeekStart::Sunday,
+            },
+            Self::GitLab => PlatformConfig {
+                weeks: 53,
+                week_start: WeekStart::Monday,
+            },
+            Self::Gitea => PlatformConfig {
+                weeks: 52,
+                week_start: WeekStart::Sunday,
+            },
+        }
+    }
+
+    /// The name that `--platform` takes.
+    #[inline]
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::GitHub => "github",
+            Self::GitLab => "gitlab",
+            Self::Gitea => "gitea",
+        }
+    }
+}
+
+/// A table of every platform's graph, one line each (after a header).
+#[inline]
+#[must_use]
+pub fn platform_table() -> String {
+    let mut table = format!(
+        "{:<10}{:>7}{:>7}  {}\n",
+        "platform", "weeks", "days", "week start"
+    );
+    for platform in Platform::ALL {
+        let config = platform.config();
+        let week_start = match config.week_start {
+            WeekStart::Sunday => "sunday",
+            WeekStart::Monday => "monday",
+        };
+        table.push_str(&format!(
+            "{:<10}{:>7}{:>7}  {week_start}\n",
+            platform.name(),
+            config.weeks,
+            config.days(),
+        ));
+    }
+    table
+}
@@ -0,0 +1,302 @@
+//! Rendering the planned contribution graph in a terminal or as an image.
+
+use {
+    crate::{CommitEntry, CommitPlan, ContributionGrid, WeekStart},
+    chrono::{Datelike as _, NaiveDate, Weekday},
+    image::{Rgba, RgbaImage},
+    std::{
+        fmt::Write as _,
+        io::{self, IsTerminal as _},
+    },
+};
+
+/// Characters from empty to full.
+const BLOCKS: [char; 5] = [' ', '░', '▒', '▓', '█'];
+/// Characters from empty to full for terminals without ANSI support.
+const DIGITS: [char; 5] = ['0', '1', '2', '3', '4'];
+/// Column and row (within its two-by-four block) of each dot of a braille character, by bit.
+const BRAILLE_DOTS: [(usize, usize); 8] = [
+    (0, 0),
+    (0, 1),
+    (0, 2),
+    (1, 0),
+    (1, 1),
+    (1, 2),
+    (0, 3),
+    (1, 3),
+];
+/// ANSI 256-color codes (roughly GitHub's greens), from empty to full.
+const COLORS: [u8; 5] = [236, 22, 28, 34, 40];
+/// GitHub's contribution-graph colors, from empty to full.
+const GREENS: [&str; 5] = ["#ebedf0", "#9be9a8", "#40c463", "#30a14e", "#216e39"];
+/// Width (and height) of one day's square, in pixels.
+const CELL: u32 = 11;
+/// Space between squares, in pixels.
+const GAP: u32 = 2;
+/// Height of the month labels above the grid in an SVG, in pixels.
+const LABEL_HEIGHT: u32 = 15;
+
+/// Render one line per day of the week (Sunday first), one cell per column,
+/// with one of five levels proportional to each cell's share of `scale`.
+/// Uses colored blocks if standard output is a terminal and plain digits otherwise.
+#[inline]
+#[must_use]
+pub fn preview_grid(grid: &ContributionGrid, scale: u8) -> String {
+    render_preview(grid, scale, io::stdout().is_terminal())
+}
+
+/// Like `preview_grid`, but with ANSI colors decided by `ansi`
+/// instead of by whether standard output is a terminal.
+#[inline]
+#[must_use]
+pub fn render_preview(grid: &ContributionGrid, scale: u8, ansi: bool) -> String {
+    let mut s = String::new();
+    for row in 0..grid.rows {
+        for col in 0..grid.cols {
+            let level = preview_level(grid.get(col, row), scale);
+            if ansi {
+                s.push_str(&format!("\x1b[38;5;{}m", COLORS[level]));
+                s.push(BLOCKS[level]);
+                s.push(BLOCKS[level]);
+            } else {
+                s.push(DIGITS[level]);
+            }
+        }
+        if ansi {
+            s.push_str("\x1b[0m");
+        }
+        s.push('\n');
+    }
+    s
+}
+
+/// How `ContributionGrid::to_ascii` draws each cell.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AsciiStyle {
+    /// One of five shaded blocks (` ░▒▓█`), by brightness.
+    Blocks,
+    /// One braille character per two columns and four rows, with a dot for each cell at least half bright.
+    Braille,
+    /// One of five digits (`0` to `4`), by brightness.
+    Digits,
+    /// The exact brightness as two hexadecimal digits, with a space between cells.
+    Hex,
+}
+
+impl ContributionGrid {
+    /// Render one line per row of `grid` (or, with `AsciiStyle::Braille`, per four rows).
+    #[inline]
+    #[must_use]
+    pub fn to_ascii(grid: &Self, style: AsciiStyle) -> String {
+        let mut s = String::new();
+        match style {
+            AsciiStyle::Blocks | AsciiStyle::Digits => {
+                let chars = if style == AsciiStyle::Blocks {
+                    BLOCKS
+                } else {
+                    DIGITS
+                };
+                for row in 0..grid.rows {
+                    for col in 0..grid.cols {
+                        s.push(chars[preview_level(grid.get(col, row), u8::MAX)]);
+                    }
+                    s.push('\n');
+                }
+            }
+            AsciiStyle::Braille => {
+                for row in (0..grid.rows).step_by(4) {
+                    for col in (0..grid.cols).step_by(2) {
+                        let mut dots = 0_u32;
+                        for (bit, (dx, dy)) in BRAILLE_DOTS.into_iter().enumerate() {
+                            if grid.get(col + dx, row + dy) >= 128 {
+                                dots |= 1 << bit;
+                            }
+                        }
+                        s.push(char::from_u32(0x2800 + dots).unwrap_or(' '));
+                    }
+                    s.push('\n');
+                }
+            }
+            AsciiStyle::Hex => {
+                for row in 0..grid.rows {
+                    let cells: Vec<String> = (0..grid.cols)
+                        .map(|col| format!("{:02x}", grid.get(col, row)))
+                        .collect();
+                    s.push_str(&cells.join(" "));
+                    s.push('\n');
+                }
+            }
+        }
+        s
+    }
+}
+
+/// Render `grid` as a table of two-digit hexadecimal brightnesses:
+/// a header of column (week) numbers from 1, then one line per row,
+/// labeled with its day of the week (counting from `week_start`).
+#[inline]
+#[must_use]
+pub fn render_columns(grid: &ContributionGrid, week_start: WeekStart) -> String {
+    let mut s = String::from("   ");
+    for col in 1..=grid.cols {
+        let _: Result<_, _> = write!(s, " {col:>2}");
+    }
+    s.push('\n');
+    let mut day = match week_start {
+        WeekStart::Sunday => Weekday::Sun,
+        WeekStart::Monday => Weekday::Mon,
+    };
+    for row in 0..grid.rows {
+        let _: Result<_, _> = write!(s, "{day}");
+        for col in 0..grid.cols {
+            let _: Result<_, _> = write!(s, " {:02x}", grid.get(col, row));
+        }
+        s.push('\n');
+        day = day.succ();
+    }
+    s
+}
+
+/// Which of the five levels `count` falls into,
+/// where any nonzero count is at least level 1.
+#[inline]
+fn preview_level(count: u8, scale: u8) -> usize {
+    if scale == 0 {
+        return 0;
+    }
+    let level = (usize::from(count) * 4).div_ceil(usize::from(scale));
+    level.min(4)
+}
+
+/// Render `plan` as a standalone SVG in the style of GitHub's contribution calendar:
+/// one square per day (one column per week, `week_start` on top), labeled with month names,
+/// and shaded by each day's share of the busiest day.
+#[inline]
+#[must_use]
+pub fn render_svg(plan: &CommitPlan, week_start: WeekStart) -> String {
+    let cells = plan_cells(plan, week_start);
+    let n_columns = cells.last().map_or(0, |&(column, _, _)| column + 1);
+    let width = n_columns * (CELL + GAP);
+    let height = LABEL_HEIGHT + 7 * (CELL + GAP);
+    let scale = max_count(plan);
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\" font-family=\"sans-serif\" font-size=\"10\">\n"
+    );
+    let mut last_month = None;
+    for &(column, row, entry) in &cells {
+        // Label the first column and each column whose week starts in a new month:
+        if row == 0 || last_month.is_none() {
+            let month = entry.date.month();
+            if last_month != Some(month) {
+                last_month = Some(month);
+                let _: Result<_, _> = writeln!(
+                    svg,
+                    "  <text x=\"{}\" y=\"{}\" fill=\"#767676\">{}</text>",
+                    column * (CELL + GAP),
+                    LABEL_HEIGHT - 5,
+                    entry.date.format("%b"),
+                );
+            }
+        }
+    }
+    for &(column, row, entry) in &cells {
+        let _: Result<_, _> = writeln!(
+            svg,
+            "  <rect x=\"{}\" y=\"{}\" width=\"{CELL}\" height=\"{CELL}\" rx=\"2\" fill=\"{}\"><title>{}: {} commit{}</title></rect>",
+            column * (CELL + GAP),
+            LABEL_HEIGHT + row * (CELL + GAP),
+            GREENS[preview_level(entry.commit_count, scale)],
+            entry.date,
+            entry.commit_count,
+            if entry.commit_count == 1 { "" } else { "s" },
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Render `plan` as an image of GitHub's contribution graph (without labels):
+/// one square per day, one column per week (`week_start` on top), separated by transparent gaps,
+/// and shaded by each day's share of the busiest day.
+#[inline]
+#[must_use]
+pub fn render_preview_png(plan: &CommitPlan, week_start: WeekStart) -> RgbaImage {
+    let cells = plan_cells(plan, week_start);
+    let n_columns = cells.last().map_or(0, |&(column, _, _)| column + 1);
+    let scale = max_count(plan);
+    let mut img = RgbaImage::new(
+        (n_columns * (CELL + GAP)).saturating_sub(GAP),
+        7 * (CELL + GAP) - GAP,
+    );
+    for &(column, row, entry) in &cells {
+        let color = rgba(GREENS[preview_level(entry.commit_count, scale)]);
+        for dx in 0..CELL {
+            for dy in 0..CELL {
+                let () = img.put_pixel(column * (CELL + GAP) + dx, row * (CELL + GAP) + dy, color);
+            }
+        }
+    }
+    img
+}
+
+/// Parse a `#rrggbb` color as opaque.
+#[inline]
+fn rgba(hex: &str) -> Rgba<u8> {
+    let channel = |i: usize| {
+        hex.get(i..i + 2)
+            .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+            .unwrap_or(0)
+    };
+    Rgba([channel(1), channel(3), channel(5), u8::MAX])
+}
+
+/// The greatest number of commits on any day in `plan`.
+#[inline]
+fn max_count(plan: &CommitPlan) -> u8 {
+    plan.entries
+        .iter()
+        .map(|entry| entry.commit_count)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Each day's number of commits in `plan`, one column per week (`week_start` on top, as in `render_svg`),
+/// with any day that `plan` doesn't list black.
+#[inline]
+#[must_use]
+pub fn plan_grid(plan: &CommitPlan, week_start: WeekStart) -> ContributionGrid {
+    let cells = plan_cells(plan, week_start);
+    let n_columns = cells
+        .last()
+        .map_or(0, |&(column, _, _)| column as usize + 1);
+    let mut grid = ContributionGrid::new(n_columns, 7);
+    for (column, row, entry) in cells {
+        if let Some(cell) = grid
+            .cells
+            .get_mut(column as usize)
+            .and_then(|cells| cells.get_mut(row as usize))
+        {
+            *cell = entry.commit_count;
+        }
+    }
+    grid
+}
+
+/// Each entry in `plan` with its column (week, counting from the first)
+/// and row (day of the week, counting from `week_start`).
+#[inline]
+fn plan_cells(plan: &CommitPlan, week_start: WeekStart) -> Vec<(u32, u32, &CommitEntry)> {
+    let Some(first) = plan.entries.first() else {
+        return vec![];
+    };
+    let first_week: NaiveDate = week_start.week_of(first.date).unwrap_or(first.date);
+    plan.entries
+        .iter()
+        .map(|entry| {
+            let days = entry.date.signed_duration_since(first_week).num_days();
+            let column = u32::try_from(days / 7).unwrap_or(u32::MAX);
+            (column, week_start.row(entry.date.weekday()), entry)
+        })
+        .collect()
+}
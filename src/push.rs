@@ -0,0 +1,96 @@
+//! Pushing the drawing to a remote once it's done.
+
+use {
+    crate::ContributronError,
+    git2::{Cred, CredentialType, PushOptions, RemoteCallbacks},
+    std::{env, path::PathBuf},
+};
+
+/// Push `reference` (or, if symbolic like `HEAD`, whatever it points to) to `remote`,
+/// which is either the name of a configured remote or a URL.
+/// Authenticates with the SSH agent, then `~/.ssh/id_ed25519`, then `~/.ssh/id_rsa`.
+/// # Errors
+/// If the remote doesn't exist or the push fails (including if it's rejected).
+#[inline]
+pub fn push(
+    repo: &git2::Repository,
+    reference: &str,
+    remote: &str,
+    force: bool,
+) -> Result<(), ContributronError> {
+    let mut remote = match repo.find_remote(remote) {
+        Ok(ok) => ok,
+        Err(_) if remote.contains(':') || remote.contains('/') => {
+            match repo.remote_anonymous(remote) {
+                Ok(ok) => ok,
+                Err(_) => return Err(ContributronError::RemoteNotFound(remote.to_owned())),
+            }
+        }
+        Err(_) => return Err(ContributronError::RemoteNotFound(remote.to_owned())),
+    };
+
+    let name = match repo.find_reference(reference) {
+        Ok(found) => match found.symbolic_target() {
+            Some(target) => target.to_owned(),
+            None => reference.to_owned(),
+        },
+        Err(source) => {
+            return Err(ContributronError::ReferenceNotFound {
+                reference: reference.to_owned(),
+                source,
+            });
+        }
+    };
+    let refspec = format!("{}{name}:{name}", if force { "+" } else { "" });
+
+    let mut callbacks = RemoteCallbacks::new();
+    let _: &mut _ = callbacks.credentials(credentials());
+    let _: &mut _ = callbacks.push_update_reference(|refname, status| match status {
+        None => Ok(()),
+        Some(message) => Err(git2::Error::from_str(&format!(
+            "`{refname}` was rejected: {message}"
+        ))),
+    });
+    let mut options = PushOptions::new();
+    let _: &mut _ = options.remote_callbacks(callbacks);
+
+    match remote.push(&[refspec], Some(&mut options)) {
+        Ok(()) => Ok(()),
+        Err(e) => Err(ContributronError::PushFailed(e)),
+    }
+}
+
+/// Credential callback trying, in order, the SSH agent and then each default SSH key.
+/// Each is tried at most once, since libgit2 calls this again after each failure.
+#[inline]
+fn credentials() -> impl FnMut(&str, Option<&str>, CredentialType) -> Result<Cred, git2::Error> {
+    let mut tried_agent = false;
+    // Popped from the back, so `id_ed25519` goes first:
+    let mut keys: Vec<PathBuf> = match env::home_dir() {
+        Some(home) => ["id_rsa", "id_ed25519"]
+            .into_iter()
+            .map(|key| home.join(".ssh").join(key))
+            .collect(),
+        None => vec![],
+    };
+    move |_url, username_from_url, allowed| {
+        let username = username_from_url.unwrap_or("git");
+        if allowed.contains(CredentialType::SSH_KEY) {
+            if !tried_agent {
+                tried_agent = true;
+                return Cred::ssh_key_from_agent(username);
+            }
+            while let Some(key) = keys.pop() {
+                if key.exists() {
+                    return Cred::ssh_key(username, None, &key, None);
+                }
+            }
+        }
+        if allowed.contains(CredentialType::DEFAULT) {
+            return Cred::default();
+        }
+        Err(git2::Error::from_str(
+            "Ran out of credentials to try (SSH agent, `~/.ssh/id_ed25519`, `~/.ssh/id_rsa`)",
+        ))
+    }
+}
@@ -0,0 +1,168 @@
+//! Checking an image for problems before spending minutes drawing it.
+
+use {
+    crate::{ContributronError, Platform, commit_counts},
+    core::fmt,
+    image::{GenericImageView as _, Pixel as _},
+    std::path::{Path, PathBuf},
+};
+
+/// Opacity below which `generate` (by default) reads a pixel as black (see `Args::alpha_threshold`).
+const DEFAULT_ALPHA_THRESHOLD: u8 = 128;
+
+/// Arguments for `verify-image`.
+#[derive(Debug, clap::Args)]
+#[non_exhaustive]
+pub struct VerifyImageArgs {
+    /// Image to check.
+    #[arg(short, long)]
+    pub image: PathBuf,
+    /// Number of commits for a white pixel (as in `generate --scale`).
+    #[arg(short, long, default_value_t = 10, value_parser = clap::value_parser!(u8).range(1..))]
+    pub scale: u8,
+    /// Black out every pixel darker than this first (as in `generate --threshold`).
+    #[arg(long, default_value_t = 0)]
+    pub threshold: u8,
+    /// Platform whose graph to check the width against (as in `generate --platform`).
+    #[arg(long, value_enum, default_value_t = Platform::GitHub)]
+    pub platform: Platform,
+}
+
+/// What `verify_image` found out about an image.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct ImageReport {
+    /// Width, in pixels.
+    pub width: u32,
+    /// Height, in pixels.
+    pub height: u32,
+    /// How its pixels are stored (e.g. `Rgba8`).
+    pub color_mode: String,
+    /// Darkest pixel.
+    pub min_luma: u8,
+    /// Brightest pixel.
+    pub max_luma: u8,
+    /// Average brightness of every pixel.
+    pub mean_luma: f32,
+    /// Number of pixels that aren't black.
+    pub nonzero_pixels: u64,
+    /// Number of commits that one copy of the image would make.
+    pub total_commits: u64,
+    /// Anything that would stop the image from drawing as expected.
+    pub warnings: Vec<String>,
+}
+
+impl fmt::Display for ImageReport {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            width,
+            height,
+            ref color_mode,
+            min_luma,
+            max_luma,
+            mean_luma,
+            nonzero_pixels,
+            total_commits,
+            warnings: _,
+        } = *self;
+        writeln!(f, "Dimensions: {width}x{height}")?;
+        writeln!(f, "Color mode: {color_mode}")?;
+        writeln!(
+            f,
+            "Luma: {min_luma} to {max_luma} ({mean_luma:.1} on average)"
+        )?;
+        writeln!(
+            f,
+            "Lit pixels: {nonzero_pixels} of {}",
+            u64::from(width) * u64::from(height),
+        )?;
+        write!(f, "Commits: {total_commits} per copy of the image")
+    }
+}
+
+/// Load `image`, black out anything transparent (as `generate` does by default) or darker than `threshold`,
+/// and summarize the result at `scale` commits for a white pixel on a graph `weeks` columns wide.
+/// # Errors
+/// If the image can't be opened.
+#[inline]
+pub fn verify_image(
+    image: &Path,
+    scale: u8,
+    threshold: u8,
+    weeks: u16,
+) -> Result<ImageReport, ContributronError> {
+    let img = match image::open(image) {
+        Ok(ok) => ok,
+        Err(source) => {
+            return Err(ContributronError::ImageOpen {
+                path: image.to_path_buf(),
+                source,
+            });
+        }
+    };
+    let (width, height) = img.dimensions();
+    let lumas: Vec<u8> = img
+        .pixels()
+        .map(|(_, _, pixel)| {
+            let [_, _, _, alpha] = pixel.0;
+            let [luma] = pixel.to_luma().0;
+            if alpha < DEFAULT_ALPHA_THRESHOLD || luma < threshold {
+                0
+            } else {
+                luma
+            }
+        })
+        .collect();
+    let nonzero_pixels = lumas.iter().filter(|&&luma| luma > 0).count() as u64;
+    let total_commits = commit_counts(&lumas, scale, None)
+        .into_iter()
+        .map(u64::from)
+        .sum();
+
+    let mut warnings = vec![];
+    if height != 7 {
+        warnings.push(format!(
+            "image is {height}px tall, so it will require `--resize` (or `--rotate`) to be drawn"
+        ));
+    }
+    if width > u32::from(weeks) {
+        warnings.push(format!(
+            "image is {width}px wide, but the graph is only {weeks} weeks wide, so it won't all fit"
+        ));
+    }
+    if nonzero_pixels == 0 {
+        warnings.push("every pixel is black, so nothing would be drawn".to_owned());
+    } else if total_commits == 0 {
+        warnings.push(format!(
+            "every pixel is too dark to make a commit at `--scale {scale}`"
+        ));
+    }
+    if img.color().has_alpha() {
+        let transparent = img
+            .pixels()
+            .filter(|&(_, _, pixel)| pixel.0[3] < DEFAULT_ALPHA_THRESHOLD)
+            .count();
+        if transparent > 0 {
+            warnings.push(format!(
+                "{transparent} pixels are mostly transparent, so they'll be black (see `--alpha-threshold`)"
+            ));
+        }
+    }
+
+    Ok(ImageReport {
+        width,
+        height,
+        color_mode: format!("{:?}", img.color()),
+        min_luma: lumas.iter().copied().min().unwrap_or(0),
+        max_luma: lumas.iter().copied().max().unwrap_or(0),
+        mean_luma: if lumas.is_empty() {
+            0.
+        } else {
+            lumas.iter().map(|&luma| f64::from(luma)).sum::<f64>() as f32 / lumas.len() as f32
+        },
+        nonzero_pixels,
+        total_commits,
+        warnings,
+    })
+}
@@ -0,0 +1,123 @@
+//! Drawing again whenever the image changes.
+
+use {
+    crate::{Args, ContributronError, run},
+    chrono::{DateTime, Utc},
+    notify::{EventKind, RecursiveMode, Watcher as _},
+    std::{
+        collections::BTreeSet,
+        fs,
+        path::Path,
+        sync::{
+            Arc,
+            atomic::{AtomicBool, Ordering},
+            mpsc,
+        },
+        time::Duration,
+    },
+};
+
+/// How long the image has to stay unchanged before drawing again,
+/// so that an editor's partial writes don't each trigger a run.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// How often to check for Ctrl-C while waiting for changes.
+const POLL: Duration = Duration::from_millis(100);
+
+/// Draw (see `run`), then draw again every time one of `--image` changes, until Ctrl-C.
+/// Each run recreates the repository (with `--force`), adds whatever's new (with `--append` or `--update`),
+/// or just plans (with `--dry-run`); a run that fails (e.g. on a half-saved image) is reported and skipped.
+/// # Errors
+/// If there's no image to watch, if the first run fails, or if an image can't be watched.
+#[inline]
+pub fn watch(args: &Args) -> Result<(), ContributronError> {
+    if args.image.is_empty() {
+        return Err(ContributronError::InvalidArgCombination(
+            "`--watch` only applies to `--image`, which wasn't given".to_owned(),
+        ));
+    }
+    let mut images = vec![];
+    for image in &args.image {
+        match fs::canonicalize(image) {
+            Ok(ok) => images.push(ok),
+            Err(source) => {
+                return Err(ContributronError::Io {
+                    context: format!("Couldn't find `{}`", image.to_string_lossy()),
+                    source,
+                });
+            }
+        }
+    }
+    // Editors often save by replacing the file, so watch its directory for anything new at its path:
+    let directories: BTreeSet<&Path> = images
+        .iter()
+        .map(|image| image.parent().unwrap_or(Path::new("/")))
+        .collect();
+    let () = run(args.clone())?;
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let stop_handler = Arc::clone(&stop);
+    match ctrlc::set_handler(move || stop_handler.store(true, Ordering::Relaxed)) {
+        Ok(()) => {}
+        Err(e) => {
+            return Err(ContributronError::WatchFailed(format!(
+                "couldn't handle Ctrl-C: {e}"
+            )));
+        }
+    }
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = match notify::recommended_watcher(tx) {
+        Ok(ok) => ok,
+        Err(e) => return Err(ContributronError::WatchFailed(e.to_string())),
+    };
+    for &directory in &directories {
+        match watcher.watch(directory, RecursiveMode::NonRecursive) {
+            Ok(()) => {}
+            Err(e) => {
+                return Err(ContributronError::WatchFailed(format!(
+                    "couldn't watch `{}`: {e}",
+                    directory.to_string_lossy(),
+                )));
+            }
+        }
+    }
+    if !args.quiet {
+        eprintln!("Watching for changes (Ctrl-C to stop)");
+    }
+
+    let mut changed = None;
+    while !stop.load(Ordering::Relaxed) {
+        match rx.recv_timeout(if changed.is_some() { DEBOUNCE } else { POLL }) {
+            Ok(Ok(event)) => {
+                if matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_))
+                    && let Some(image) = images.iter().find(|&image| event.paths.contains(image))
+                {
+                    changed = Some(image);
+                }
+            }
+            Ok(Err(e)) => return Err(ContributronError::WatchFailed(e.to_string())),
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                if let Some(image) = changed.take() {
+                    let modified = fs::metadata(image)
+                        .and_then(|metadata| metadata.modified())
+                        .map_or_else(
+                            |e| e.to_string(),
+                            |time| DateTime::<Utc>::from(time).to_string(),
+                        );
+                    eprintln!("[changed] `{}` at {modified}", image.to_string_lossy());
+                    if let Err(e) = run(args.clone()) {
+                        eprintln!("{e}");
+                    }
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+    }
+    for directory in directories {
+        match watcher.unwatch(directory) {
+            Ok(()) => {}
+            Err(e) => return Err(ContributronError::WatchFailed(e.to_string())),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,83 @@
+//! GitHub Actions workflows that draw on a schedule.
+
+use crate::{Args, ContributronError};
+
+/// Written by `--generate-workflow`, with `{command}` replaced by the full command line.
+const TEMPLATE: &str = "\
+# Written by `contributron generate --generate-workflow`.
+name: contributron
+on:
+  schedule:
+    - cron: '0 9 * * 1'
+  workflow_dispatch:
+jobs:
+  draw:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: cargo install contributron
+      - run: |
+          {command}
+";
+
+/// A GitHub Actions workflow that runs `contributron generate` with every one of `args`
+/// (except `--config` and `--profile`, whose values `args` already include)
+/// every Monday at 09:00 UTC.
+/// # Errors
+/// If any argument can't be written back out as a command-line argument.
+#[inline]
+pub fn render_workflow(args: &Args) -> Result<String, ContributronError> {
+    let table = match toml::Table::try_from(args) {
+        Ok(ok) => ok,
+        Err(e) => {
+            return Err(ContributronError::InvalidArgument(format!(
+                "Internal error: couldn't convert the arguments to TOML: {e}"
+            )));
+        }
+    };
+    let mut command = vec!["contributron generate".to_owned()];
+    for (key, value) in table {
+        let flag = format!("--{}", key.replace('_', "-"));
+        let value = match value {
+            toml::Value::Boolean(false) => continue,
+            toml::Value::Boolean(true) => {
+                let () = command.push(flag);
+                continue;
+            }
+            toml::Value::String(s) => s,
+            toml::Value::Integer(_) | toml::Value::Float(_) => value.to_string(),
+            toml::Value::Array(values) => {
+                for value in values {
+                    let toml::Value::String(s) = value else {
+                        return Err(ContributronError::InvalidArgument(format!(
+                            "Internal error: argument `{key}` has unsupported value {value}"
+                        )));
+                    };
+                    let () = command.push(format!("{flag}={}", shell_quote(&s)));
+                }
+                continue;
+            }
+            toml::Value::Datetime(_) | toml::Value::Table(_) => {
+                return Err(ContributronError::InvalidArgument(format!(
+                    "Internal error: argument `{key}` has unsupported value {value}"
+                )));
+            }
+        };
+        let () = command.push(format!("{flag}={}", shell_quote(&value)));
+    }
+    Ok(TEMPLATE.replace("{command}", &command.join(" \\\n            ")))
+}
+
+/// Quote `s` for a POSIX shell, unless it's clearly safe as is.
+#[inline]
+fn shell_quote(s: &str) -> String {
+    if !s.is_empty()
+        && s.chars().all(|c| {
+            c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/' | ':' | '@' | '+')
+        })
+    {
+        s.to_owned()
+    } else {
+        format!("'{}'", s.replace('\'', r"'\''"))
+    }
+}
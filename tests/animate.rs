@@ -0,0 +1,59 @@
+use contributron::{ContributronError, run_cli};
+
+#[test]
+fn each_year_gets_the_next_image() {
+    let dir = tempfile::tempdir().unwrap();
+    let solid = dir.path().join("solid.pgm");
+    let () = image::GrayImage::from_pixel(1, 7, image::Luma([255]))
+        .save(&solid)
+        .unwrap();
+    let sundays = dir.path().join("sundays.pgm");
+    let () = image::GrayImage::from_fn(1, 7, |_, y| image::Luma([if y == 0 { 255 } else { 0 }]))
+        .save(&sundays)
+        .unwrap();
+    let csv = dir.path().join("plan.csv");
+    let repo = dir.path().join("repo");
+    let run_with = |extra: &[&str]| {
+        let argv = [
+            "contributron",
+            "plan",
+            "--repo",
+            repo.to_str().unwrap(),
+            "--image",
+            solid.to_str().unwrap(),
+            "--image",
+            sundays.to_str().unwrap(),
+            "--name",
+            "Test",
+            "--email",
+            "test@example.com",
+            "--scale",
+            "1",
+            "--start-date",
+            "2023-01-01",
+            "--end-date",
+            "2025-01-11",
+            "--allow-long-range",
+            "--output-csv",
+            csv.to_str().unwrap(),
+        ];
+        run_cli(argv.iter().chain(extra))
+    };
+    let result = run_with(&[]);
+    assert!(
+        matches!(result, Err(ContributronError::InvalidArgCombination(_))),
+        "{result:?}",
+    );
+    let () = run_with(&["--animate"]).unwrap();
+    let csv = std::fs::read_to_string(&csv).unwrap();
+    let drawn = |date: &str| csv.lines().any(|line| line.starts_with(date));
+    // 2023 is solid every other week:
+    assert!(drawn("2023-01-01") && drawn("2023-01-07"));
+    assert!(!drawn("2023-01-08"));
+    // 2024 (from the week of January 1) is only Sundays:
+    assert!(drawn("2023-12-31"));
+    assert!(!drawn("2024-01-01"));
+    assert!(drawn("2024-01-14") && !drawn("2024-01-15"));
+    // And 2025 starts over (solid):
+    assert!(drawn("2024-12-29") && drawn("2024-12-30"));
+}
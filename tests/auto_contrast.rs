@@ -0,0 +1,15 @@
+use contributron::{ContributionGrid, auto_contrast};
+
+#[test]
+fn narrow_band_is_stretched() {
+    let mut columns = ContributionGrid::from(vec![[100, 110, 120, 130, 140, 150, 100]]);
+    let () = auto_contrast(&mut columns);
+    assert_eq!(columns.cells, [[0, 51, 102, 153, 204, 255, 0]]);
+}
+
+#[test]
+fn flat_image_is_unchanged() {
+    let mut columns = ContributionGrid::from(vec![[128; 7]; 2]);
+    let () = auto_contrast(&mut columns);
+    assert_eq!(columns.cells, [[128; 7]; 2]);
+}
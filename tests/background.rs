@@ -0,0 +1,53 @@
+use contributron::{ContributionGrid, fill_background};
+
+#[test]
+fn only_black_pixels_are_filled() {
+    let mut columns = ContributionGrid::from(vec![[0, 1, 255, 0, 0, 0, 0], [0; 7]]);
+    let () = fill_background(&mut columns, 9, &[]);
+    assert_eq!(columns.cells, [[9, 1, 255, 9, 9, 9, 9], [9; 7]]);
+}
+
+#[test]
+fn blank_rows_are_not_filled() {
+    let mut columns = ContributionGrid::from(vec![[0, 1, 255, 0, 0, 0, 0]]);
+    let () = fill_background(&mut columns, 9, &[0, 6]);
+    assert_eq!(columns.cells, [[0, 1, 255, 9, 9, 9, 0]]);
+}
+
+#[test]
+fn background_is_filled_after_the_weekday_options() {
+    let dir = tempfile::tempdir().unwrap();
+    let image = dir.path().join("image.pgm");
+    let () = image::GrayImage::new(1, 7).save(&image).unwrap();
+    let drawn = |extra: &str| {
+        let grid_png = dir.path().join(format!("grid{extra}.png"));
+        let repo = dir.path().join(format!("repo{extra}"));
+        let () = contributron::run_cli([
+            "contributron",
+            "plan",
+            "--repo",
+            repo.to_str().unwrap(),
+            "--image",
+            image.to_str().unwrap(),
+            "--name",
+            "Test",
+            "--email",
+            "test@example.com",
+            "--quiet",
+            "--background",
+            "20",
+            extra,
+            "--output-grid-png",
+            grid_png.to_str().unwrap(),
+        ])
+        .unwrap();
+        let drawn = image::open(&grid_png).unwrap().to_luma8();
+        (0..7)
+            .map(|y| drawn.get_pixel(0, y).0[0])
+            .collect::<Vec<_>>()
+    };
+    // Not boosted on weekends (Sunday and Saturday, on top and at the bottom):
+    assert_eq!(drawn("--weekend-boost=3"), [20; 7]);
+    // Not drawn on days left blank:
+    assert_eq!(drawn("--weekday-only"), [0, 20, 20, 20, 20, 20, 0]);
+}
@@ -0,0 +1,227 @@
+use contributron::{
+    BuiltinKind, Commands, Config, ContributronError, DitherMode, init_config, load_config,
+    load_profile, parse_args, write_completions, write_man_page,
+};
+
+fn config(dir: &tempfile::TempDir, contents: &str) -> String {
+    let path = dir.path().join("config.toml");
+    let () = std::fs::write(&path, contents).unwrap();
+    path.to_str().unwrap().to_owned()
+}
+
+#[test]
+fn template_is_a_valid_empty_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("nested").join("config.toml");
+    let () = init_config(&path).unwrap();
+    assert_eq!(load_config(&path).unwrap(), Config::default());
+    // Never overwrite:
+    assert!(init_config(&path).is_err());
+}
+
+#[test]
+fn command_line_takes_precedence() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = config(
+        &dir,
+        "name = \"Config\"\nemail = \"config@example.com\"\ntimezone = -300\ndither = \"bayer\"\n",
+    );
+    let invocation = parse_args([
+        "contributron",
+        "--repo",
+        "repo",
+        "--image",
+        "image.png",
+        "--name",
+        "Command Line",
+        "--config",
+        &path,
+    ])
+    .unwrap();
+    let Commands::Generate(generate) = invocation else {
+        panic!("{invocation:?}");
+    };
+    let args = contributron::Args::from(*generate);
+    assert_eq!(args.common.name, "Command Line");
+    assert_eq!(args.common.email, "config@example.com");
+    assert_eq!(args.timezone, -300);
+    assert_eq!(args.dither, Some(DitherMode::Bayer));
+}
+
+#[test]
+fn unknown_keys_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = config(&dir, "nmae = \"Typo\"\n");
+    assert!(load_config(path.as_ref()).is_err());
+}
+
+#[test]
+fn config_can_say_what_to_draw() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = config(&dir, "pattern = \"solid\"\n");
+    let parse = |extra: &[&str]| {
+        let argv = [
+            "contributron",
+            "-r",
+            "repo",
+            "-n",
+            "Test",
+            "-e",
+            "test@example.com",
+        ];
+        let invocation = parse_args(argv.iter().chain(extra)).unwrap();
+        let Commands::Generate(generate) = invocation else {
+            panic!("{invocation:?}");
+        };
+        contributron::Args::from(*generate)
+    };
+    let args = parse(&["--config", &path]);
+    assert_eq!(args.pattern, Some(BuiltinKind::Solid));
+    assert!(args.image.is_empty());
+    // But anything to draw on the command line replaces it:
+    let args = parse(&["--config", &path, "--image", "image.png"]);
+    assert_eq!(args.pattern, None);
+    assert_eq!(args.image, [std::path::PathBuf::from("image.png")]);
+}
+
+#[test]
+fn profiles_override_the_config_but_not_the_command_line() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = config(
+        &dir,
+        "name = \"Config\"\nemail = \"config@example.com\"\ntimezone = 60\n\n\
+         [profiles.work]\nname = \"Work\"\nemail = \"work@example.com\"\n\n\
+         [profiles.home]\ngit_reference = \"refs/heads/home\"\n",
+    );
+    let loaded = load_config(path.as_ref()).unwrap();
+    assert_eq!(loaded.profiles.keys().collect::<Vec<_>>(), ["home", "work"],);
+    assert_eq!(
+        load_profile(&loaded, "work").unwrap().name.as_deref(),
+        Some("Work")
+    );
+    assert!(matches!(
+        load_profile(&loaded, "play"),
+        Err(ContributronError::UnknownProfile(_)),
+    ));
+    let invocation = parse_args([
+        "contributron",
+        "--repo",
+        "repo",
+        "--image",
+        "image.png",
+        "--email",
+        "command-line@example.com",
+        "--config",
+        &path,
+        "--profile",
+        "work",
+    ])
+    .unwrap();
+    let Commands::Generate(generate) = invocation else {
+        panic!("{invocation:?}");
+    };
+    let args = contributron::Args::from(*generate);
+    assert_eq!(args.common.name, "Work");
+    assert_eq!(args.common.email, "command-line@example.com");
+    assert_eq!(args.timezone, 60);
+}
+
+#[test]
+fn subcommands_say_how_far_to_go() {
+    let parse = |subcommand: Option<&str>| {
+        let argv = [
+            "-r",
+            "repo",
+            "-n",
+            "Test",
+            "-e",
+            "test@example.com",
+            "--pattern",
+            "solid",
+        ];
+        parse_args(
+            std::iter::once("contributron")
+                .chain(subcommand)
+                .chain(argv),
+        )
+        .unwrap()
+    };
+    let Commands::Preview(preview) = parse(Some("preview")) else {
+        panic!();
+    };
+    let args = contributron::Args::from(*preview);
+    assert!(args.dry_run && args.preview);
+    let Commands::Plan(plan) = parse(Some("plan")) else {
+        panic!();
+    };
+    let args = contributron::Args::from(*plan);
+    assert!(args.dry_run && !args.preview);
+    let Commands::Generate(generate) = parse(Some("generate")) else {
+        panic!();
+    };
+    let args = contributron::Args::from(*generate);
+    assert!(!args.dry_run && !args.preview);
+    // The old form, without a subcommand, still works (for now):
+    let Commands::Generate(generate) = parse(None) else {
+        panic!();
+    };
+    let args = contributron::Args::from(*generate);
+    assert_eq!(args.common.repo, std::path::Path::new("repo"));
+}
+
+#[test]
+fn only_generate_takes_commit_options() {
+    use clap::Parser as _;
+    let parse = |subcommand: &str, extra: &str| {
+        contributron::Cli::try_parse_from([
+            "contributron",
+            subcommand,
+            "-r",
+            "repo",
+            "-n",
+            "Test",
+            "-e",
+            "test@example.com",
+            "--pattern",
+            "solid",
+            extra,
+        ])
+    };
+    for extra in [
+        "--push=origin",
+        "--gpg-key-id=ABCD",
+        "--in-memory",
+        "--jobs=2",
+    ] {
+        assert!(parse("generate", extra).is_ok(), "{extra}");
+        assert!(parse("preview", extra).is_err(), "{extra}");
+        assert!(parse("plan", extra).is_err(), "{extra}");
+    }
+}
+
+#[test]
+fn completions_know_every_value() {
+    let mut script = vec![];
+    let () = write_completions(clap_complete::Shell::Fish, &mut script);
+    let script = String::from_utf8(script).unwrap();
+    for needle in [
+        "generate",
+        "curve",
+        "quadratic",
+        "floyd-steinberg",
+        "stripes-h",
+    ] {
+        assert!(script.contains(needle), "{needle}");
+    }
+}
+
+#[test]
+fn man_page_has_every_section() {
+    let mut page = vec![];
+    let () = write_man_page(&mut page).unwrap();
+    let page = String::from_utf8(page).unwrap();
+    for section in ["NAME", "SYNOPSIS", "DESCRIPTION", "OPTIONS", "EXAMPLES"] {
+        assert!(page.contains(&format!(".SH {section}\n")), "{section}");
+    }
+    assert!(page.contains(r"\-\-image"));
+}
@@ -0,0 +1,87 @@
+use {
+    contributron::{
+        BAYER_8X8, Curve, DitherMode, apply_curve, commit_counts, poisson_counts, posterize,
+    },
+    rand::{SeedableRng as _, rngs::SmallRng},
+};
+
+/// Eight weeks of the same pixel, one week per column.
+fn weeks(pixel: u8) -> Vec<u8> {
+    vec![pixel; 7 * 8]
+}
+
+#[test]
+fn no_dithering_rounds() {
+    let counts = commit_counts(&[0, 63, 64, 191, 192, 255], 2, None);
+    assert_eq!(counts, [0, 0, 1, 1, 2, 2]);
+}
+
+#[test]
+fn bayer_three_levels() {
+    // At scale 2, these pixels ideally make 0, (just over) 1/2, and 2 commits:
+    let [black, gray, white] =
+        [0, 64, 255].map(|pixel| commit_counts(&weeks(pixel), 2, Some(DitherMode::Bayer)));
+    assert!(black.iter().all(|&count| count == 0), "{black:?}");
+    assert!(white.iter().all(|&count| count == 2), "{white:?}");
+    for (index, &count) in gray.iter().enumerate() {
+        let (column, row) = (index / 7, index % 7);
+        let expected = u8::from(BAYER_8X8[row][column] < 32);
+        assert_eq!(count, expected, "at column {column}, row {row}");
+    }
+    // Spot-check the dot pattern itself:
+    assert_eq!(gray[0], 1); // Column 0, row 0: threshold 0
+    assert_eq!(gray[7], 0); // Column 1, row 0: threshold 32
+    assert_eq!(gray[1], 0); // Column 0, row 1: threshold 48
+    assert_eq!(gray[8], 1); // Column 1, row 1: threshold 16
+}
+
+#[test]
+fn floyd_steinberg_preserves_total() {
+    let pixels = weeks(64);
+    let counts = commit_counts(&pixels, 2, Some(DitherMode::FloydSteinberg));
+    let total: u32 = counts.iter().copied().map(u32::from).sum();
+    // Ideally (just over) half a commit per day:
+    let ideal = pixels.len() / 2;
+    assert!(total.abs_diff(ideal as u32) <= 7, "{total} vs. {ideal}");
+    assert!(counts.iter().all(|&count| count <= 1), "{counts:?}");
+}
+
+#[test]
+fn curves() {
+    for curve in [Curve::Linear, Curve::Sqrt, Curve::Log, Curve::Quadratic] {
+        assert_eq!(apply_curve(0, 10, curve), 0, "{curve:?}");
+        assert_eq!(apply_curve(255, 10, curve), 10, "{curve:?}");
+    }
+    assert_eq!(apply_curve(64, 100, Curve::Linear), 25);
+    assert_eq!(apply_curve(64, 100, Curve::Sqrt), 50);
+    assert_eq!(apply_curve(64, 100, Curve::Quadratic), 6);
+    assert_eq!(apply_curve(15, 100, Curve::Log), 50);
+}
+
+#[test]
+fn posterize_to_five_levels() {
+    let levels: Vec<u8> = [0, 31, 32, 100, 128, 200, 224, 255]
+        .into_iter()
+        .map(|pixel| posterize(pixel, 5))
+        .collect();
+    assert_eq!(levels, [0, 0, 64, 128, 128, 191, 255, 255]);
+    assert_eq!(posterize(77, 2), 0);
+    assert_eq!(posterize(128, 2), 255);
+}
+
+#[test]
+fn poisson_counts_average_out_to_the_scale() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let pixels = [255; 10_000];
+    let counts = poisson_counts(&pixels, 4, &mut rng);
+    let mean = counts.iter().map(|&count| f64::from(count)).sum::<f64>() / 10_000.;
+    assert!((mean - 4.).abs() < 0.1, "{mean}");
+    // Around 2% of days would round to 0 and 5% to 8 or more:
+    assert!(counts.contains(&0));
+    assert!(counts.iter().any(|&count| count >= 8));
+    assert_eq!(poisson_counts(&[0; 100], 4, &mut rng), [0; 100]);
+    assert_eq!(
+        poisson_counts(&pixels, 4, &mut SmallRng::seed_from_u64(0)),
+        counts,
+    );
+}
@@ -0,0 +1,862 @@
+use {
+    chrono::NaiveDate,
+    contributron::{
+        ContributronError, EMPTY_TREE, GitInfo, commit_tree, count_commits_per_day, draw_pixel,
+        draw_repeating_pattern, gaussian_times, git_log_to_pattern, init_repo, last_committed_date,
+        open_repo, spread_times, tag_boundaries, undo,
+    },
+};
+
+fn git(dir: &tempfile::TempDir, dry_run: bool) -> GitInfo<'static, 'static, 'static> {
+    GitInfo {
+        repo: init_repo(&dir.path().join("repo"), false).unwrap(),
+        reference: "HEAD",
+        name: "Test",
+        email: "test@example.com",
+        author_name: "Test",
+        author_email: "test@example.com",
+        dry_run,
+        offset_minutes: 0,
+        gpg: None,
+        quiet: true,
+        messages: vec![contributron::DEFAULT_MESSAGE.to_owned()],
+        commits_made: std::cell::Cell::new(0),
+        commit_time: chrono::NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+        spread_hours: None,
+        gaussian_minutes: None,
+        jitter_secs: 0,
+        rng: None,
+        min_commits: 1,
+        max_commits: None,
+        poisson: false,
+        verbose: false,
+        content_file: None,
+        readme_template: None,
+        image_name: String::new(),
+        checkpoint: None,
+    }
+}
+
+fn commits(repo: &git2::Repository) -> Vec<git2::Commit<'_>> {
+    let Ok(head) = repo.head() else {
+        return vec![];
+    };
+    let mut commits = vec![head.peel_to_commit().unwrap()];
+    while let Ok(parent) = commits.last().unwrap().parent(0) {
+        commits.push(parent);
+    }
+    commits
+}
+
+#[test]
+fn draw_pixel_makes_a_linear_history() {
+    let dir = tempfile::tempdir().unwrap();
+    let git = git(&dir, false);
+    let date = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let () = draw_pixel(&git, &commit_tree(&git).unwrap(), 128, 3, date).unwrap();
+
+    let commits = commits(&git.repo);
+    assert_eq!(commits.len(), 3);
+    for commit in &commits {
+        assert!(commit.parent_count() <= 1);
+        let time = commit.author().when().seconds();
+        let day = chrono::DateTime::from_timestamp(time, 0)
+            .unwrap()
+            .date_naive();
+        assert_eq!(day, date);
+    }
+}
+
+#[test]
+fn dry_run_makes_no_commits() {
+    let dir = tempfile::tempdir().unwrap();
+    let git = git(&dir, true);
+    let date = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let () = draw_pixel(&git, &commit_tree(&git).unwrap(), 128, 3, date).unwrap();
+    assert!(commits(&git.repo).is_empty());
+}
+
+#[test]
+fn draw_repeating_pattern_covers_every_day() {
+    let dir = tempfile::tempdir().unwrap();
+    let git = git(&dir, false);
+    let start = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let end = NaiveDate::from_ymd_opt(2024, 3, 23).unwrap();
+    // One column of ones, then the blank separator column:
+    let () = draw_repeating_pattern(
+        &git,
+        &[[255; 7]],
+        start..=end,
+        1,
+        None,
+        #[cfg(feature = "progress")]
+        None,
+    )
+    .unwrap();
+    assert_eq!(commits(&git.repo).len(), 7);
+}
+
+#[test]
+fn messages_cycle_across_days() {
+    let dir = tempfile::tempdir().unwrap();
+    let git = GitInfo {
+        messages: vec!["a".to_owned(), "b".to_owned(), "c".to_owned()],
+        ..git(&dir, false)
+    };
+    let date = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let tree = commit_tree(&git).unwrap();
+    let () = draw_pixel(&git, &tree, 128, 2, date).unwrap();
+    let () = draw_pixel(&git, &tree, 128, 2, date.succ_opt().unwrap()).unwrap();
+    let messages: Vec<_> = commits(&git.repo)
+        .iter()
+        .rev()
+        .map(|commit| commit.message().unwrap().to_owned())
+        .collect();
+    assert_eq!(messages, ["a", "b", "c", "a"]);
+}
+
+#[test]
+fn jitter_stays_within_the_day_in_order() {
+    use rand::SeedableRng as _;
+
+    let dir = tempfile::tempdir().unwrap();
+    let git = GitInfo {
+        jitter_secs: u32::MAX,
+        rng: Some(rand::rngs::SmallRng::seed_from_u64(0).into()),
+        ..git(&dir, false)
+    };
+    let date = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let () = draw_pixel(&git, &commit_tree(&git).unwrap(), 255, 20, date).unwrap();
+    let times: Vec<_> = commits(&git.repo)
+        .iter()
+        .rev()
+        .map(|commit| commit.author().when().seconds())
+        .collect();
+    assert!(times.is_sorted(), "{times:?}");
+    assert!(times.windows(2).any(|pair| pair[0] != pair[1]), "{times:?}");
+    for time in times {
+        let day = chrono::DateTime::from_timestamp(time, 0)
+            .unwrap()
+            .date_naive();
+        assert_eq!(day, date);
+    }
+}
+
+#[test]
+fn commit_time_moves_every_commit_and_bounds_jitter() {
+    use rand::SeedableRng as _;
+
+    let dir = tempfile::tempdir().unwrap();
+    let late = chrono::NaiveTime::from_hms_opt(23, 30, 15).unwrap();
+    let date = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let exact = GitInfo {
+        commit_time: late,
+        ..git(&dir, false)
+    };
+    let () = draw_pixel(&exact, &commit_tree(&exact).unwrap(), 255, 1, date).unwrap();
+    assert_eq!(
+        commits(&exact.repo)[0].author().when().seconds(),
+        date.and_time(late).and_utc().timestamp(),
+    );
+
+    let dir = tempfile::tempdir().unwrap();
+    let git = GitInfo {
+        commit_time: late,
+        jitter_secs: 60 * 60,
+        rng: Some(rand::rngs::SmallRng::seed_from_u64(0).into()),
+        ..git(&dir, false)
+    };
+    let () = draw_pixel(&git, &commit_tree(&git).unwrap(), 255, 50, date).unwrap();
+    for commit in commits(&git.repo) {
+        let time = chrono::DateTime::from_timestamp(commit.author().when().seconds(), 0).unwrap();
+        assert_eq!(time.date_naive(), date);
+        assert!(time.time() >= chrono::NaiveTime::from_hms_opt(22, 30, 15).unwrap());
+    }
+}
+
+#[test]
+fn spread_commits_are_evenly_spaced_within_the_day() {
+    let time = |h, m, s| chrono::NaiveTime::from_hms_opt(h, m, s).unwrap();
+    assert_eq!(
+        spread_times(5, time(12, 0, 0), 8.),
+        [
+            time(8, 0, 0),
+            time(10, 0, 0),
+            time(12, 0, 0),
+            time(14, 0, 0),
+            time(16, 0, 0),
+        ],
+    );
+    assert_eq!(spread_times(1, time(12, 0, 0), 8.), [time(12, 0, 0)]);
+    assert_eq!(
+        spread_times(3, time(23, 0, 0), 8.),
+        [time(19, 0, 0), time(23, 0, 0), time(23, 59, 59)],
+    );
+
+    let dir = tempfile::tempdir().unwrap();
+    let git = GitInfo {
+        spread_hours: Some(2.),
+        ..git(&dir, false)
+    };
+    let date = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let () = draw_pixel(&git, &commit_tree(&git).unwrap(), 255, 3, date).unwrap();
+    let times: Vec<_> = commits(&git.repo)
+        .iter()
+        .rev()
+        .map(|commit| {
+            chrono::DateTime::from_timestamp(commit.author().when().seconds(), 0)
+                .unwrap()
+                .time()
+        })
+        .collect();
+    assert_eq!(times, [time(11, 0, 0), time(12, 0, 0), time(13, 0, 0)]);
+}
+
+#[test]
+fn gaussian_times_cluster_around_the_commit_time() {
+    use rand::SeedableRng as _;
+
+    let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+    let noon = chrono::NaiveTime::from_hms_opt(12, 0, 0).unwrap();
+    let times = gaussian_times(200, noon, 60., &mut rng);
+    assert!(times.is_sorted());
+    let seconds: Vec<i64> = times
+        .iter()
+        .map(|&time| (time - noon).num_seconds())
+        .collect();
+    let mean = seconds.iter().sum::<i64>() / 200;
+    assert!(mean.abs() < 15 * 60, "{mean}");
+    // Within five standard deviations:
+    assert!(seconds.iter().all(|delta| delta.abs() < 5 * 60 * 60));
+    assert!(seconds.iter().any(|delta| delta.abs() > 60 * 60));
+    // Near midnight, early draws stay on the same day:
+    let just_after_midnight = chrono::NaiveTime::from_hms_opt(0, 10, 0).unwrap();
+    let times = gaussian_times(50, just_after_midnight, 60., &mut rng);
+    assert_eq!(times[0], chrono::NaiveTime::MIN);
+}
+
+#[test]
+fn max_commits_caps_each_day() {
+    let dir = tempfile::tempdir().unwrap();
+    let git = GitInfo {
+        max_commits: Some(5),
+        ..git(&dir, false)
+    };
+    let date = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let () = draw_repeating_pattern(
+        &git,
+        &[[255; 7]],
+        date..=date,
+        100,
+        None,
+        #[cfg(feature = "progress")]
+        None,
+    )
+    .unwrap();
+    assert_eq!(commits(&git.repo).len(), 5);
+}
+
+#[test]
+fn missing_reference_is_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let git = GitInfo {
+        reference: "refs/heads/nonexistent",
+        ..git(&dir, false)
+    };
+    let date = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let result = draw_pixel(&git, &commit_tree(&git).unwrap(), 128, 3, date);
+    assert!(
+        matches!(result, Err(ContributronError::ReferenceNotFound { .. })),
+        "{result:?}",
+    );
+}
+
+#[test]
+fn existing_repo_is_an_error_without_overwrite() {
+    let dir = tempfile::tempdir().unwrap();
+    let result = init_repo(dir.path(), false);
+    assert!(
+        matches!(result, Err(ContributronError::RepoExists(_))),
+        "{:?}",
+        result.map(|_| ()),
+    );
+    let () = init_repo(dir.path(), true).map(|_| ()).unwrap();
+}
+
+#[test]
+fn force_overwrites_an_existing_repo_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = dir.path().join("repo");
+    let () = std::fs::create_dir(&repo).unwrap();
+    let image = dir.path().join("image.pgm");
+    let () = image::GrayImage::from_pixel(1, 7, image::Luma([255]))
+        .save(&image)
+        .unwrap();
+    let run_with = |extra: &[&str]| {
+        let mut argv = vec![
+            "contributron".as_ref(),
+            "--repo".as_ref(),
+            repo.as_os_str(),
+            "--image".as_ref(),
+            image.as_os_str(),
+            "--name".as_ref(),
+            "Test".as_ref(),
+            "--email".as_ref(),
+            "test@example.com".as_ref(),
+            "--end-date".as_ref(),
+            "2024-03-23".as_ref(),
+            "--quiet".as_ref(),
+        ];
+        argv.extend(extra.iter().map(std::ffi::OsStr::new));
+        contributron::run_cli(argv)
+    };
+    let result = run_with(&[]);
+    assert!(
+        matches!(result, Err(ContributronError::RepoExists(_))),
+        "{result:?}",
+    );
+    let () = run_with(&["--force"]).unwrap();
+    assert!(!commits(&git2::Repository::open(&repo).unwrap()).is_empty());
+}
+
+#[test]
+fn iso8601_is_the_commit_time_in_its_time_zone() {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = dir.path().join("repo");
+    let image = dir.path().join("image.pgm");
+    let () = image::GrayImage::from_pixel(1, 7, image::Luma([255]))
+        .save(&image)
+        .unwrap();
+    let () = contributron::run_cli([
+        "contributron",
+        "--repo",
+        repo.to_str().unwrap(),
+        "--image",
+        image.to_str().unwrap(),
+        "--name",
+        "Test",
+        "--email",
+        "test@example.com",
+        "--end-date",
+        "2024-03-23",
+        "--scale",
+        "1",
+        "--commit-time",
+        "09:30:00",
+        "--timezone=-300",
+        "--message",
+        "{iso8601}",
+        "--quiet",
+    ])
+    .unwrap();
+    let repo = git2::Repository::open(&repo).unwrap();
+    let commits = commits(&repo);
+    assert!(!commits.is_empty());
+    for commit in commits {
+        let when = commit.author().when();
+        let offset = chrono::FixedOffset::east_opt(when.offset_minutes() * 60).unwrap();
+        let time = chrono::DateTime::from_timestamp(when.seconds(), 0)
+            .unwrap()
+            .with_timezone(&offset);
+        assert_eq!(commit.message(), Some(time.to_rfc3339().as_str()));
+        assert!(time.to_rfc3339().ends_with("T04:30:00-05:00"), "{time}");
+    }
+}
+
+#[test]
+fn append_builds_on_existing_history() {
+    let dir = tempfile::tempdir().unwrap();
+    let date = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let first = git(&dir, false);
+    let () = draw_pixel(&first, &commit_tree(&first).unwrap(), 255, 2, date).unwrap();
+    let appending = GitInfo {
+        repo: open_repo(&dir.path().join("repo"), "HEAD").unwrap(),
+        ..git(&tempfile::tempdir().unwrap(), false)
+    };
+    let () = draw_pixel(
+        &appending,
+        &commit_tree(&appending).unwrap(),
+        255,
+        3,
+        date.succ_opt().unwrap(),
+    )
+    .unwrap();
+    assert_eq!(commits(&appending.repo).len(), 5);
+}
+
+#[test]
+fn last_committed_date_is_in_the_committer_time_zone() {
+    let dir = tempfile::tempdir().unwrap();
+    let git = GitInfo {
+        offset_minutes: -300,
+        ..git(&dir, false)
+    };
+    assert_eq!(last_committed_date(&git.repo, "HEAD").unwrap(), None);
+    let date = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let () = draw_pixel(&git, &commit_tree(&git).unwrap(), 255, 2, date).unwrap();
+    let () = draw_pixel(
+        &git,
+        &commit_tree(&git).unwrap(),
+        255,
+        2,
+        date.pred_opt().unwrap(),
+    )
+    .unwrap();
+    assert_eq!(last_committed_date(&git.repo, "HEAD").unwrap(), Some(date));
+}
+
+#[test]
+fn append_needs_an_existing_repo_and_reference() {
+    let dir = tempfile::tempdir().unwrap();
+    let result = open_repo(&dir.path().join("nonexistent"), "HEAD");
+    assert!(
+        matches!(result, Err(ContributronError::RepoOpen { .. })),
+        "{:?}",
+        result.map(|_| ()),
+    );
+    let _: git2::Repository = init_repo(&dir.path().join("repo"), false).unwrap();
+    let result = open_repo(&dir.path().join("repo"), "refs/heads/nonexistent");
+    assert!(
+        matches!(result, Err(ContributronError::InvalidArgument(_))),
+        "{:?}",
+        result.map(|_| ()),
+    );
+}
+
+#[test]
+fn every_commit_has_the_commit_tree() {
+    let dir = tempfile::tempdir().unwrap();
+    let git = git(&dir, false);
+    assert_eq!(commit_tree(&git).unwrap().id().to_string(), EMPTY_TREE);
+    let date = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let () = draw_pixel(&git, &commit_tree(&git).unwrap(), 128, 2, date).unwrap();
+    for commit in commits(&git.repo) {
+        assert_eq!(commit.tree_id().to_string(), EMPTY_TREE);
+    }
+}
+
+#[test]
+fn content_file_is_in_every_commit() {
+    let dir = tempfile::tempdir().unwrap();
+    let content = dir.path().join("notes.txt");
+    let () = std::fs::write(&content, "hello\n").unwrap();
+    let git = GitInfo {
+        content_file: Some(content),
+        ..git(&dir, false)
+    };
+    let date = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let () = draw_pixel(&git, &commit_tree(&git).unwrap(), 128, 2, date).unwrap();
+    for commit in commits(&git.repo) {
+        let entry = commit.tree().unwrap().get_name("notes.txt").unwrap().id();
+        assert_eq!(git.repo.find_blob(entry).unwrap().content(), b"hello\n");
+    }
+}
+
+#[test]
+fn content_file_needs_a_working_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let content = dir.path().join("notes.txt");
+    let () = std::fs::write(&content, "hello\n").unwrap();
+    let git = GitInfo {
+        repo: git2::Repository::init_bare(dir.path().join("bare")).unwrap(),
+        content_file: Some(content),
+        ..git(&dir, false)
+    };
+    let result = commit_tree(&git);
+    assert!(
+        matches!(result, Err(ContributronError::WorkdirNotAvailable(_))),
+        "{:?}",
+        result.map(|_| ()),
+    );
+}
+
+#[test]
+fn readme_changes_commit_by_commit() {
+    let dir = tempfile::tempdir().unwrap();
+    let git = GitInfo {
+        readme_template: Some("{date} #{commit_index}".to_owned()),
+        ..git(&dir, false)
+    };
+    let date = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let () = draw_pixel(&git, &commit_tree(&git).unwrap(), 128, 2, date).unwrap();
+    let readmes: Vec<_> = commits(&git.repo)
+        .iter()
+        .rev()
+        .map(|commit| {
+            let entry = commit.tree().unwrap().get_name("README.md").unwrap().id();
+            git.repo.find_blob(entry).unwrap().content().to_owned()
+        })
+        .collect();
+    assert_eq!(readmes, [b"2024-03-10 #1", b"2024-03-10 #2"]);
+}
+
+#[test]
+fn author_can_differ_from_committer() {
+    let dir = tempfile::tempdir().unwrap();
+    let git = GitInfo {
+        author_name: "Author",
+        author_email: "author@example.com",
+        ..git(&dir, false)
+    };
+    let date = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let () = draw_pixel(&git, &commit_tree(&git).unwrap(), 255, 1, date).unwrap();
+    let commit = git.repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(commit.author().name(), Some("Author"));
+    assert_eq!(commit.author().email(), Some("author@example.com"));
+    assert_eq!(commit.committer().name(), Some("Test"));
+    assert_eq!(commit.committer().email(), Some("test@example.com"));
+    assert_eq!(commit.author().when(), commit.committer().when());
+}
+
+#[test]
+fn tags_mark_the_new_commits() {
+    let dir = tempfile::tempdir().unwrap();
+    let git = git(&dir, false);
+    let date = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let () = draw_pixel(&git, &commit_tree(&git).unwrap(), 255, 2, date).unwrap();
+    let before = git.repo.head().unwrap().target();
+    let () = draw_pixel(
+        &git,
+        &commit_tree(&git).unwrap(),
+        255,
+        3,
+        date.succ_opt().unwrap(),
+    )
+    .unwrap();
+    let () = tag_boundaries(&git, before, "art", false).unwrap();
+    let commits = commits(&git.repo);
+    let tagged = |name: &str| {
+        git.repo
+            .find_reference(&format!("refs/tags/{name}"))
+            .unwrap()
+            .target()
+    };
+    assert_eq!(tagged("art-end"), Some(commits[0].id()));
+    assert_eq!(tagged("art-start"), Some(commits[2].id()));
+    // Existing tags stay put without `force`:
+    assert!(matches!(
+        tag_boundaries(&git, None, "art", false),
+        Err(ContributronError::Git { .. }),
+    ));
+    let () = tag_boundaries(&git, None, "art", true).unwrap();
+    assert_eq!(tagged("art-start"), Some(commits[4].id()));
+}
+
+#[test]
+fn undo_resets_to_before_the_last_run() {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = dir.path().join("repo");
+    let run_with = |extra: &[&str]| {
+        let mut argv = vec![
+            "contributron".as_ref(),
+            "--repo".as_ref(),
+            repo.as_os_str(),
+            "--pattern".as_ref(),
+            "solid".as_ref(),
+            "--scale".as_ref(),
+            "1".as_ref(),
+            "--name".as_ref(),
+            "Test".as_ref(),
+            "--email".as_ref(),
+            "test@example.com".as_ref(),
+            "--quiet".as_ref(),
+        ];
+        argv.extend(extra.iter().map(std::ffi::OsStr::new));
+        contributron::run_cli(argv)
+    };
+    let () = run_with(&["--end-date", "2024-03-16"]).unwrap();
+    let head = || {
+        git2::Repository::open(&repo)
+            .unwrap()
+            .head()
+            .unwrap()
+            .target()
+    };
+    let before = head();
+    let () = run_with(&["--end-date", "2024-03-30", "--append"]).unwrap();
+    assert_ne!(head(), before);
+    let (reference, oid) = undo(&repo).unwrap();
+    assert!(reference.starts_with("refs/heads/"), "{reference}");
+    assert_eq!(oid, before);
+    assert_eq!(head(), before);
+    let result = undo(&repo);
+    assert!(
+        matches!(result, Err(ContributronError::NothingToUndo(_))),
+        "{result:?}",
+    );
+}
+
+#[test]
+fn undo_deletes_a_reference_that_had_no_commits() {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = dir.path().join("repo");
+    let () = contributron::run_cli([
+        "contributron",
+        "--repo",
+        repo.to_str().unwrap(),
+        "--pattern",
+        "solid",
+        "--scale",
+        "1",
+        "--name",
+        "Test",
+        "--email",
+        "test@example.com",
+        "--end-date",
+        "2024-03-16",
+        "--quiet",
+    ])
+    .unwrap();
+    let (reference, oid) = undo(&repo).unwrap();
+    assert!(reference.starts_with("refs/heads/"), "{reference}");
+    assert_eq!(oid, None);
+    let opened = git2::Repository::open(&repo).unwrap();
+    assert!(opened.find_reference(&reference).is_err());
+    assert!(opened.head().is_err());
+}
+
+#[test]
+fn update_adds_only_missing_commits() {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = dir.path().join("repo");
+    let run_with = |extra: &[&str]| {
+        let argv = [
+            "contributron",
+            "generate",
+            "--repo",
+            repo.to_str().unwrap(),
+            "--pattern",
+            "solid",
+            "--name",
+            "Test",
+            "--email",
+            "test@example.com",
+            "--start-date",
+            "2024-03-10",
+            "--end-date",
+            "2024-03-11",
+            "--quiet",
+        ];
+        contributron::run_cli(argv.iter().chain(extra))
+    };
+    let () = run_with(&["--scale", "2"]).unwrap();
+    let () = run_with(&["--scale", "3", "--update"]).unwrap();
+    let counts = || count_commits_per_day(&git2::Repository::open(&repo).unwrap(), "HEAD").unwrap();
+    let sunday = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    assert_eq!(counts().len(), 2);
+    assert_eq!(counts()[&sunday], 3);
+    assert_eq!(counts()[&sunday.succ_opt().unwrap()], 3);
+    // Nothing's missing now:
+    let () = run_with(&["--scale", "3", "--update"]).unwrap();
+    assert_eq!(counts()[&sunday], 3);
+}
+
+#[test]
+fn jobs_make_the_same_history_in_parts() {
+    let dir = tempfile::tempdir().unwrap();
+    let history = |jobs: &str| {
+        let repo = dir.path().join(format!("repo-{jobs}"));
+        let () = contributron::run_cli([
+            "contributron",
+            "generate",
+            "--repo",
+            repo.to_str().unwrap(),
+            "--pattern",
+            "checkerboard",
+            "--name",
+            "Test",
+            "--email",
+            "test@example.com",
+            "--start-date",
+            "2024-03-10",
+            "--end-date",
+            "2024-04-20",
+            "--message",
+            "{date} #{index}",
+            "--jobs",
+            jobs,
+            "--quiet",
+        ])
+        .unwrap();
+        let git = git2::Repository::open(&repo).unwrap();
+        let branches: Vec<_> = git
+            .references()
+            .unwrap()
+            .map(|reference| reference.unwrap().name().unwrap().to_owned())
+            .collect();
+        assert!(
+            branches.iter().all(|name| !name.contains("part-")),
+            "{branches:?}",
+        );
+        let mut revwalk = git.revwalk().unwrap();
+        let () = revwalk.push_head().unwrap();
+        let () = revwalk.set_sorting(git2::Sort::TOPOLOGICAL).unwrap();
+        revwalk
+            .map(|oid| {
+                let commit = git.find_commit(oid.unwrap()).unwrap();
+                assert!(commit.parent_count() <= 1);
+                (
+                    commit.time().seconds(),
+                    commit.message().unwrap().to_owned(),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    let sequential = history("1");
+    assert!(!sequential.is_empty());
+    assert_eq!(history("3"), sequential);
+}
+
+#[test]
+fn in_memory_writes_one_pack() {
+    let dir = tempfile::tempdir().unwrap();
+    let draw = |name: &str, extra: &[&str]| {
+        let repo = dir.path().join(name);
+        let argv = [
+            "contributron",
+            "generate",
+            "--repo",
+            repo.to_str().unwrap(),
+            "--pattern",
+            "solid",
+            "--name",
+            "Test",
+            "--email",
+            "test@example.com",
+            "--start-date",
+            "2024-03-10",
+            "--end-date",
+            "2024-03-16",
+            "--tag-prefix",
+            "v",
+            "--quiet",
+        ];
+        let () = contributron::run_cli(argv.iter().chain(extra)).unwrap();
+        repo
+    };
+    let on_disk = draw("on-disk", &[]);
+    let in_memory = draw("in-memory", &["--in-memory"]);
+    let objects = in_memory.join(".git").join("objects");
+    let loose: Vec<_> = std::fs::read_dir(&objects)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .filter(|name| name != "pack" && name != "info")
+        .collect();
+    assert!(loose.is_empty(), "{loose:?}");
+    let packs = std::fs::read_dir(objects.join("pack"))
+        .unwrap()
+        .filter(|entry| entry.as_ref().unwrap().path().extension().unwrap() == "pack")
+        .count();
+    assert_eq!(packs, 1);
+    let history = |repo: &std::path::Path| {
+        let git = git2::Repository::open(repo).unwrap();
+        let head = git.head().unwrap().peel_to_commit().unwrap().id();
+        let tags = git.tag_names(None).unwrap().len();
+        (head, tags, count_commits_per_day(&git, "HEAD").unwrap())
+    };
+    assert_eq!(history(&in_memory), history(&on_disk));
+}
+
+#[test]
+fn in_memory_writes_its_pack_even_if_a_later_step_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = dir.path().join("repo");
+    let run_with = |extra: &[&str]| {
+        let argv = [
+            "contributron",
+            "generate",
+            "--repo",
+            repo.to_str().unwrap(),
+            "--pattern",
+            "solid",
+            "--name",
+            "Test",
+            "--email",
+            "test@example.com",
+            "--start-date",
+            "2024-03-10",
+            "--tag-prefix",
+            "v",
+            "--quiet",
+        ];
+        contributron::run_cli(argv.iter().chain(extra))
+    };
+    let () = run_with(&["--end-date", "2024-03-16"]).unwrap();
+    let before = git2::Repository::open(&repo)
+        .unwrap()
+        .head()
+        .unwrap()
+        .target()
+        .unwrap();
+    // Both tags already exist, so tagging fails after everything's drawn (but before it's on disk):
+    let result = run_with(&["--end-date", "2024-03-30", "--append", "--in-memory"]);
+    assert!(
+        matches!(result, Err(ContributronError::Git { .. })),
+        "{result:?}",
+    );
+    let git = git2::Repository::open(&repo).unwrap();
+    let head = git.head().unwrap().peel_to_commit().unwrap().id();
+    assert_ne!(head, before);
+    // Every commit the branch now points to is on disk, all the way back:
+    assert!(git.find_commit(before).is_ok());
+    assert_eq!(count_commits_per_day(&git, "HEAD").unwrap().len(), 21);
+    let (_, oid) = undo(&repo).unwrap();
+    assert_eq!(oid, Some(before));
+}
+
+#[test]
+fn from_git_log_mirrors_another_repo() {
+    let dir = tempfile::tempdir().unwrap();
+    let per_day = dir.path().join("per-day.csv");
+    let () = std::fs::write(
+        &per_day,
+        "date,count\n2024-03-10,1\n2024-03-11,2\n2024-03-12,4\n2024-03-20,8\n",
+    )
+    .unwrap();
+    let draw = |repo: &std::path::Path, source: [&str; 2]| {
+        let () = contributron::run_cli([
+            "contributron",
+            "generate",
+            "--repo",
+            repo.to_str().unwrap(),
+            source[0],
+            source[1],
+            "--name",
+            "Test",
+            "--email",
+            "test@example.com",
+            "--scale",
+            "2",
+            "--start-date",
+            "2024-03-10",
+            "--end-date",
+            "2024-03-16",
+            "--quiet",
+        ])
+        .unwrap();
+        git2::Repository::open(repo).unwrap()
+    };
+    let private = dir.path().join("private");
+    let source = draw(&private, ["--per-day-file", per_day.to_str().unwrap()]);
+    let sunday = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let tuesday = NaiveDate::from_ymd_opt(2024, 3, 12).unwrap();
+    // Out of 4 on the busiest day, rounded:
+    let pattern = git_log_to_pattern(&source, sunday, tuesday, 2).unwrap();
+    assert_eq!(pattern.len(), 3);
+    assert_eq!(pattern[&sunday], 1);
+    assert_eq!(pattern[&sunday.succ_opt().unwrap()], 1);
+    assert_eq!(pattern[&tuesday], 2);
+    assert_eq!(
+        git_log_to_pattern(&source, sunday, sunday, 2).unwrap()[&sunday],
+        2
+    );
+
+    let public = draw(
+        &dir.path().join("public"),
+        ["--from-git-log", private.to_str().unwrap()],
+    );
+    let counts = count_commits_per_day(&public, "HEAD").unwrap();
+    assert_eq!(counts.len(), 3);
+    assert_eq!(counts[&sunday], 1);
+    assert_eq!(counts[&tuesday], 2);
+}
@@ -0,0 +1,48 @@
+use contributron::{Commands, ContributionGrid, ContributronError, gamma_correct, parse_args, run};
+
+#[test]
+fn gamma_two_darkens_mid_gray() {
+    let mut columns = ContributionGrid::from(vec![[0, 128, 255, 128, 128, 128, 128]]);
+    let () = gamma_correct(&mut columns, 2.);
+    let [black, gray, white] = [0, 1, 2].map(|row| columns.get(0, row));
+    assert_eq!(black, 0);
+    assert_eq!(white, 255);
+    assert!(gray.abs_diff(65) <= 1, "{gray}");
+}
+
+#[test]
+fn gamma_one_is_a_no_op() {
+    let original = [[0, 1, 2, 127, 128, 254, 255]];
+    let mut columns = ContributionGrid::from(original.to_vec());
+    let () = gamma_correct(&mut columns, 1.);
+    assert_eq!(columns.cells, original);
+}
+
+#[test]
+fn nonpositive_gamma_is_rejected() {
+    for gamma in ["0", "-1"] {
+        let invocation = parse_args([
+            "contributron",
+            "--repo",
+            "repo",
+            "--image",
+            "image.png",
+            "--name",
+            "Test",
+            "--email",
+            "test@example.com",
+            "--gamma",
+            gamma,
+        ])
+        .unwrap();
+        let Commands::Generate(generate) = invocation else {
+            panic!("{invocation:?}");
+        };
+        let args = contributron::Args::from(*generate);
+        let result = run(args);
+        assert!(
+            matches!(result, Err(ContributronError::InvalidArgument(_))),
+            "{result:?}",
+        );
+    }
+}
@@ -0,0 +1,83 @@
+#![cfg(unix)]
+
+use {
+    chrono::NaiveDate,
+    contributron::{ContributronError, GitInfo, GpgSigner, commit_tree, draw_pixel, init_repo},
+    std::{fs, os::unix::fs::PermissionsExt as _, path::PathBuf},
+};
+
+const FAKE_SIGNATURE: &str = "-----BEGIN PGP SIGNATURE-----\n\nfake\n-----END PGP SIGNATURE-----\n";
+
+/// Write an executable shell script standing in for `gpg`.
+fn mock_gpg(dir: &tempfile::TempDir, script: &str) -> PathBuf {
+    let path = dir.path().join("gpg");
+    let () = fs::write(&path, format!("#!/bin/sh\n{script}\n")).unwrap();
+    let () = fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    path
+}
+
+fn git(dir: &tempfile::TempDir, program: PathBuf) -> GitInfo<'static, 'static, 'static> {
+    GitInfo {
+        repo: init_repo(&dir.path().join("repo"), false).unwrap(),
+        reference: "HEAD",
+        name: "Test",
+        email: "test@example.com",
+        author_name: "Test",
+        author_email: "test@example.com",
+        dry_run: false,
+        offset_minutes: 0,
+        gpg: Some(GpgSigner {
+            key_id: "0123456789ABCDEF".to_owned(),
+            program,
+        }),
+        quiet: true,
+        messages: vec![contributron::DEFAULT_MESSAGE.to_owned()],
+        commits_made: std::cell::Cell::new(0),
+        commit_time: chrono::NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+        spread_hours: None,
+        gaussian_minutes: None,
+        jitter_secs: 0,
+        rng: None,
+        min_commits: 1,
+        max_commits: None,
+        poisson: false,
+        verbose: false,
+        content_file: None,
+        readme_template: None,
+        image_name: String::new(),
+        checkpoint: None,
+    }
+}
+
+#[test]
+fn commits_are_signed_and_reference_moves() {
+    let dir = tempfile::tempdir().unwrap();
+    let program = mock_gpg(
+        &dir,
+        &format!("cat > /dev/null\nprintf '%s' '{FAKE_SIGNATURE}'"),
+    );
+    let git = git(&dir, program);
+    let date = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let () = draw_pixel(&git, &commit_tree(&git).unwrap(), 128, 2, date).unwrap();
+
+    let head = git.repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.message(), Some("#2/128"));
+    let (signature, _) = git.repo.extract_signature(&head.id(), None).unwrap();
+    assert_eq!(signature.as_str(), Some(FAKE_SIGNATURE));
+    let parent = head.parent(0).unwrap();
+    assert_eq!(parent.message(), Some("#1/128"));
+    assert_eq!(parent.parent_count(), 0);
+}
+
+#[test]
+fn missing_key_is_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let program = mock_gpg(&dir, "echo 'gpg: skipped: No secret key' >&2\nexit 2");
+    let git = git(&dir, program);
+    let date = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let result = draw_pixel(&git, &commit_tree(&git).unwrap(), 128, 1, date);
+    assert!(
+        matches!(result, Err(ContributronError::GpgSigningFailed(ref message)) if message.contains("No secret key")),
+        "{result:?}",
+    );
+}
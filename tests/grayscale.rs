@@ -0,0 +1,19 @@
+use {
+    contributron::{GrayscaleMethod, to_grayscale},
+    image::{DynamicImage, Rgb, RgbImage},
+};
+
+#[test]
+fn grayscale_methods() {
+    let img = DynamicImage::ImageRgb8(RgbImage::from_fn(2, 1, |x, _| {
+        if x == 0 {
+            Rgb([255, 0, 0])
+        } else {
+            Rgb([30, 60, 90])
+        }
+    }));
+    let gray = |method| to_grayscale(&img, method).into_raw();
+    assert_eq!(gray(GrayscaleMethod::Luminosity), [54, 56]);
+    assert_eq!(gray(GrayscaleMethod::Average), [85, 60]);
+    assert_eq!(gray(GrayscaleMethod::Lightness), [127, 60]);
+}
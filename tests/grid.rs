@@ -0,0 +1,133 @@
+use contributron::{ContributionGrid, ContributronError, MergeMode, PatternSource as _, run_cli};
+
+#[test]
+fn new_grid_is_blank() {
+    let grid = ContributionGrid::new(3, 5);
+    assert_eq!((grid.cols, grid.rows), (3, 5));
+    assert!(grid.iter_columns().all(|column| column == [0; 5]));
+    assert_eq!(grid.iter_columns().count(), 3);
+}
+
+#[test]
+fn from_image_reads_every_row() {
+    let img = image::DynamicImage::ImageLuma8(image::GrayImage::from_fn(2, 9, |x, y| {
+        image::Luma([u8::try_from(10 * x + y).unwrap()])
+    }));
+    let grid = ContributionGrid::from_image(&img).unwrap();
+    assert_eq!((grid.cols, grid.rows), (2, 9));
+    assert_eq!(grid.get(1, 8), 18);
+    assert_eq!(grid.get(0, 3), 3);
+    // Outside the grid is black:
+    assert_eq!(grid.get(2, 0), 0);
+    assert_eq!(grid.get(0, 9), 0);
+    // As a pattern, it keeps every row (though only the first seven of each column are drawn):
+    assert_eq!(grid.columns(), grid);
+}
+
+#[test]
+fn converts_from_columns() {
+    let columns = vec![[1, 2, 3, 4, 5, 6, 7], [0; 7]];
+    let grid = ContributionGrid::from(columns.clone());
+    assert_eq!((grid.cols, grid.rows), (2, 7));
+    assert_eq!(grid.get(0, 6), 7);
+    assert_eq!(grid.columns().cells, columns);
+}
+
+#[test]
+fn merge_combines_cell_by_cell() {
+    let base = ContributionGrid::from(vec![[0, 100, 200, 255, 128, 255, 0]]);
+    let overlay = ContributionGrid::from(vec![[50, 100, 100, 255, 128, 0, 0]]);
+    let merged = |mode| {
+        ContributionGrid::merge(&base, &overlay, mode)
+            .unwrap()
+            .cells
+    };
+    assert_eq!(merged(MergeMode::Add), [[50, 200, 255, 255, 255, 255, 0]]);
+    assert_eq!(merged(MergeMode::Max), [[50, 100, 200, 255, 128, 255, 0]]);
+    assert_eq!(merged(MergeMode::Min), [[0, 100, 100, 255, 128, 0, 0]]);
+    assert_eq!(merged(MergeMode::Multiply), [[0, 39, 78, 255, 64, 0, 0]]);
+    assert_eq!(
+        merged(MergeMode::Screen),
+        [[50, 161, 222, 255, 192, 255, 0]]
+    );
+}
+
+#[test]
+fn merge_rejects_different_sizes() {
+    let err = ContributionGrid::merge(
+        &ContributionGrid::new(3, 7),
+        &ContributionGrid::new(2, 7),
+        MergeMode::Max,
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ContributronError::GridDimensionMismatch {
+            base: (3, 7),
+            overlay: (2, 7),
+        }
+    ));
+}
+
+#[test]
+fn tile_repeats_columns() {
+    let grid = ContributionGrid::from(vec![[1; 7], [2; 7], [3; 7]]);
+    let tiled = ContributionGrid::tile(&grid, 8);
+    assert_eq!((tiled.cols, tiled.rows), (8, 7));
+    assert_eq!(tiled.cells[6], tiled.cells[0]);
+    assert_eq!(
+        tiled.cells,
+        [
+            [1; 7], [2; 7], [3; 7], [1; 7], [2; 7], [3; 7], [1; 7], [2; 7]
+        ],
+    );
+    assert_eq!(
+        ContributionGrid::tile(&ContributionGrid::new(0, 7), 2),
+        ContributionGrid::new(2, 7),
+    );
+}
+
+#[test]
+fn to_image_round_trips() {
+    let img = image::DynamicImage::ImageLuma8(image::GrayImage::from_fn(4, 9, |x, y| {
+        image::Luma([u8::try_from(30 * x + y).unwrap()])
+    }));
+    let grid = ContributionGrid::from_image(&img).unwrap();
+    let drawn = ContributionGrid::to_image(&grid);
+    assert_eq!(drawn.dimensions(), (4, 9));
+    assert_eq!(drawn.get_pixel(3, 8).0, [98]);
+    let again = ContributionGrid::from_image(&image::DynamicImage::ImageLuma8(drawn)).unwrap();
+    assert_eq!(again, grid);
+}
+
+#[test]
+fn output_grid_png_writes_the_preprocessed_pattern() {
+    let dir = tempfile::tempdir().unwrap();
+    let image = dir.path().join("image.pgm");
+    let () = image::GrayImage::from_fn(3, 7, |x, _| image::Luma([if x == 1 { 200 } else { 0 }]))
+        .save(&image)
+        .unwrap();
+    let grid_png = dir.path().join("grid.png");
+    let repo = dir.path().join("repo");
+    let () = run_cli([
+        "contributron",
+        "plan",
+        "--repo",
+        repo.to_str().unwrap(),
+        "--image",
+        image.to_str().unwrap(),
+        "--name",
+        "Test",
+        "--email",
+        "test@example.com",
+        "--invert",
+        "--quiet",
+        "--output-grid-png",
+        grid_png.to_str().unwrap(),
+    ])
+    .unwrap();
+    let drawn = image::open(&grid_png).unwrap().to_luma8();
+    assert_eq!(drawn.dimensions(), (3, 7));
+    assert_eq!(drawn.get_pixel(0, 0).0, [255]);
+    assert_eq!(drawn.get_pixel(1, 6).0, [55]);
+}
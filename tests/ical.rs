@@ -0,0 +1,59 @@
+use contributron::run_cli;
+
+#[test]
+fn one_all_day_event_per_active_day() {
+    let dir = tempfile::tempdir().unwrap();
+    let image = dir.path().join("image.pgm");
+    // Only Sunday and Monday are lit:
+    let () = image::GrayImage::from_fn(1, 7, |_, y| image::Luma([if y < 2 { 255 } else { 0 }]))
+        .save(&image)
+        .unwrap();
+    let ics = dir.path().join("plan.ics");
+    let repo = dir.path().join("repo");
+    let () = run_cli([
+        "contributron",
+        "plan",
+        "--repo",
+        repo.to_str().unwrap(),
+        "--image",
+        image.to_str().unwrap(),
+        "--name",
+        "Test",
+        "--email",
+        "test@example.com",
+        "--scale",
+        "2",
+        "--start-date",
+        "2024-03-10",
+        "--end-date",
+        "2024-03-16",
+        "--message",
+        "Day {date}; commit {index}, of {total}",
+        "--output-ical",
+        ics.to_str().unwrap(),
+    ])
+    .unwrap();
+    let ics = std::fs::read_to_string(&ics).unwrap();
+    assert!(ics.lines().all(|line| line.len() <= 75 + 1));
+    assert!(ics.split("\r\n").all(|line| !line.contains('\n')));
+    let ics = ics.replace("\r\n ", "");
+    let lines: Vec<_> = ics.split("\r\n").collect();
+    assert_eq!(
+        lines[..4],
+        [
+            "BEGIN:VCALENDAR",
+            "VERSION:2.0",
+            "PRODID:-//contributron//EN",
+            "CALSCALE:GREGORIAN",
+        ],
+    );
+    assert_eq!(
+        lines.iter().filter(|&&line| line == "BEGIN:VEVENT").count(),
+        2
+    );
+    assert!(lines.contains(&"DTSTART;VALUE=DATE:20240311"));
+    assert!(lines.contains(&"DTEND;VALUE=DATE:20240312"));
+    assert!(lines.contains(&r"SUMMARY:Day 2024-03-10\; commit 1\, of 2"));
+    assert!(lines.contains(&"DESCRIPTION:2 commits"));
+    assert_eq!(lines[lines.len() - 2..], ["END:VCALENDAR", ""]);
+}
@@ -0,0 +1,32 @@
+use {
+    contributron::{image_to_columns, rgba_to_masked_luma},
+    image::{DynamicImage, GrayImage, Luma, Rgba, RgbaImage},
+};
+
+#[test]
+fn columns_are_left_to_right_top_to_bottom() {
+    let img = GrayImage::from_fn(3, 7, |x, y| Luma([(10 * x + y) as u8]));
+    let columns = image_to_columns(&DynamicImage::ImageLuma8(img));
+    assert_eq!(
+        columns.cells,
+        [
+            [0, 1, 2, 3, 4, 5, 6],
+            [10, 11, 12, 13, 14, 15, 16],
+            [20, 21, 22, 23, 24, 25, 26],
+        ],
+    );
+}
+
+#[test]
+fn transparent_pixels_are_black() {
+    let img = RgbaImage::from_fn(1, 7, |_, y| Rgba([255, 255, 255, (y * 40) as u8]));
+    let columns = rgba_to_masked_luma(&DynamicImage::ImageRgba8(img), 128);
+    assert_eq!(columns.cells, [[0, 0, 0, 0, 255, 255, 255]]);
+}
+
+#[test]
+fn short_images_are_padded_with_black() {
+    let img = GrayImage::from_pixel(2, 3, Luma([255]));
+    let columns = image_to_columns(&DynamicImage::ImageLuma8(img));
+    assert_eq!(columns.cells, [[255, 255, 255, 0, 0, 0, 0]; 2]);
+}
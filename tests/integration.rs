@@ -0,0 +1,72 @@
+use {
+    chrono::{DateTime, Days, NaiveDate},
+    contributron::{Commands, parse_args, run},
+};
+
+#[test]
+fn checkerboard_end_to_end() {
+    let dir = tempfile::tempdir().unwrap();
+    // Alternating 0 and 128, starting with 0 in the top-left corner:
+    let image = dir.path().join("checkerboard.png");
+    let () = image::GrayImage::from_fn(2, 7, |x, y| {
+        image::Luma([if (x + y) % 2 == 0 { 0 } else { 128 }])
+    })
+    .save(&image)
+    .unwrap();
+    let repo = dir.path().join("repo");
+    let start = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let invocation = parse_args([
+        "contributron",
+        "generate",
+        "--repo",
+        repo.to_str().unwrap(),
+        "--image",
+        image.to_str().unwrap(),
+        "--name",
+        "Test",
+        "--email",
+        "test@example.com",
+        "--scale",
+        "3",
+        "--start-date",
+        "2024-03-10",
+        "--end-date",
+        "2024-03-23",
+        "--quiet",
+    ])
+    .unwrap();
+    let Commands::Generate(generate) = invocation else {
+        panic!("{invocation:?}");
+    };
+    let args = contributron::Args::from(*generate);
+    let () = run(args).unwrap();
+
+    // 128 of 255 at `--scale 3` rounds to 2 commits, and 0 to none:
+    let expected: Vec<NaiveDate> = (0_u64..14)
+        .filter(|i| (i / 7 + i % 7) % 2 == 1)
+        .flat_map(|i| [start + Days::new(i); 2])
+        .collect();
+    assert_eq!(expected.len(), 14);
+
+    let git = git2::Repository::open(&repo).unwrap();
+    let mut dates = vec![];
+    let mut commit = Some(git.head().unwrap().peel_to_commit().unwrap());
+    while let Some(current) = commit {
+        let time = current.author().when();
+        assert_eq!(time.offset_minutes(), 0);
+        let () = dates.push(
+            DateTime::from_timestamp(time.seconds(), 0)
+                .unwrap()
+                .date_naive(),
+        );
+        assert!(current.parent_count() <= 1);
+        commit = current.parents().next();
+    }
+    let () = dates.reverse();
+    assert_eq!(dates, expected);
+
+    // Following first parents from `HEAD` reached every commit, so the history is one line:
+    let mut revwalk = git.revwalk().unwrap();
+    let () = revwalk.push_head().unwrap();
+    assert_eq!(revwalk.count(), expected.len());
+}
@@ -0,0 +1,57 @@
+use contributron::{ContributionGrid, commit_counts, invert_columns};
+
+#[test]
+fn inverted_white_makes_no_commits() {
+    let mut columns = ContributionGrid::from(vec![[u8::MAX; 7]; 3]);
+    let () = invert_columns(&mut columns);
+    assert_eq!(columns.cells, [[0; 7]; 3]);
+    let pixels = columns.cells.concat();
+    for scale in [1, 10, u8::MAX] {
+        let counts = commit_counts(&pixels, scale, None);
+        assert!(counts.iter().all(|&count| count == 0), "{counts:?}");
+    }
+}
+
+#[test]
+fn inverting_twice_is_a_no_op() {
+    let original = [[0, 1, 2, 127, 128, 254, 255]];
+    let mut columns = ContributionGrid::from(original.to_vec());
+    let () = invert_columns(&mut columns);
+    assert_eq!(columns.cells, [[255, 254, 253, 128, 127, 1, 0]]);
+    let () = invert_columns(&mut columns);
+    assert_eq!(columns.cells, original);
+}
+
+#[test]
+fn invert_comes_before_threshold() {
+    let dir = tempfile::tempdir().unwrap();
+    let image = dir.path().join("image.pgm");
+    let () = image::GrayImage::from_fn(3, 7, |x, _| image::Luma([[0, 100, 200][x as usize]]))
+        .save(&image)
+        .unwrap();
+    let grid_png = dir.path().join("grid.png");
+    let repo = dir.path().join("repo");
+    let () = contributron::run_cli([
+        "contributron",
+        "plan",
+        "--repo",
+        repo.to_str().unwrap(),
+        "--image",
+        image.to_str().unwrap(),
+        "--name",
+        "Test",
+        "--email",
+        "test@example.com",
+        "--quiet",
+        "--invert",
+        "--threshold",
+        "100",
+        "--output-grid-png",
+        grid_png.to_str().unwrap(),
+    ])
+    .unwrap();
+    let drawn = image::open(&grid_png).unwrap().to_luma8();
+    // Inverted to 255, 155, and 55, then the last blacked out (rather than blacked out, then made white):
+    let row: Vec<_> = (0..3).map(|x| drawn.get_pixel(x, 0).0[0]).collect();
+    assert_eq!(row, [255, 155, 0]);
+}
@@ -0,0 +1,47 @@
+use contributron::limit_commits;
+
+#[test]
+fn faint_pixels_get_the_minimum() {
+    assert_eq!(limit_commits(10, 0, 1, None), 1);
+    assert_eq!(limit_commits(10, 0, 0, None), 0);
+    assert_eq!(limit_commits(0, 0, 3, None), 0);
+    assert_eq!(limit_commits(255, 7, 3, None), 7);
+}
+
+#[test]
+fn maximum_wins() {
+    assert_eq!(limit_commits(255, 100, 1, Some(5)), 5);
+    assert_eq!(limit_commits(1, 0, 5, Some(5)), 5);
+}
+
+#[test]
+fn max_below_min_is_rejected() {
+    let invocation = contributron::parse_args([
+        "contributron",
+        "--repo",
+        "repo",
+        "--image",
+        "image.png",
+        "--name",
+        "Test",
+        "--email",
+        "test@example.com",
+        "--min-commits",
+        "3",
+        "--max-commits",
+        "2",
+    ])
+    .unwrap();
+    let contributron::Commands::Generate(generate) = invocation else {
+        panic!("{invocation:?}");
+    };
+    let args = contributron::Args::from(*generate);
+    let result = contributron::run(args);
+    assert!(
+        matches!(
+            result,
+            Err(contributron::ContributronError::InvalidArgCombination(_))
+        ),
+        "{result:?}",
+    );
+}
@@ -0,0 +1,89 @@
+use {
+    chrono::{DateTime, FixedOffset, NaiveDate},
+    contributron::{
+        ContributronError, DEFAULT_MESSAGE, choose_messages, format_message, load_messages,
+        render_readme,
+    },
+};
+
+fn date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 3, 10).unwrap()
+}
+
+fn time() -> DateTime<FixedOffset> {
+    DateTime::parse_from_rfc3339("2024-03-10T07:30:00-05:00").unwrap()
+}
+
+#[test]
+fn default_message_is_unchanged() {
+    assert_eq!(
+        format_message(DEFAULT_MESSAGE, 2, 5, 128, date(), time()).unwrap(),
+        "#2/128"
+    );
+}
+
+#[test]
+fn every_placeholder() {
+    assert_eq!(
+        format_message(
+            "{index} of {total} on {date} ({iso8601}): {pixel} {{literal}}",
+            1,
+            3,
+            255,
+            date(),
+            time(),
+        )
+        .unwrap(),
+        "1 of 3 on 2024-03-10 (2024-03-10T07:30:00-05:00): 255 {literal}",
+    );
+}
+
+#[test]
+fn bad_templates_are_rejected() {
+    for template in ["{nope}", "{index", "index}", "{}"] {
+        let result = format_message(template, 1, 1, 0, date(), time());
+        assert!(
+            matches!(result, Err(ContributronError::InvalidMessageTemplate(_))),
+            "{template}: {result:?}",
+        );
+    }
+}
+
+#[test]
+fn message_file_skips_blank_lines() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("messages.txt");
+    let () = std::fs::write(&path, "first\n\n  \nsecond on {date}\n").unwrap();
+    assert_eq!(load_messages(&path).unwrap(), ["first", "second on {date}"]);
+
+    let () = std::fs::write(&path, "\n \n\t\n").unwrap();
+    assert!(matches!(
+        load_messages(&path),
+        Err(ContributronError::InvalidArgument(_))
+    ));
+}
+
+#[test]
+fn message_and_message_file_conflict() {
+    let result = choose_messages(Some("hi".to_owned()), Some("messages.txt".as_ref()));
+    assert!(
+        matches!(result, Err(ContributronError::ConflictingArgs(_))),
+        "{result:?}",
+    );
+    assert_eq!(choose_messages(None, None).unwrap(), [DEFAULT_MESSAGE]);
+}
+
+#[test]
+fn readme_placeholders() {
+    let date = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    assert_eq!(
+        render_readme(
+            "# {name}'s {image_name}\n\n{date}, #{commit_index} ({unknown})\n",
+            date,
+            3,
+            "logo.png",
+            "Test",
+        ),
+        "# Test's logo.png\n\n2024-03-10, #3 ({unknown})\n",
+    );
+}
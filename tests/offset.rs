@@ -0,0 +1,82 @@
+use contributron::{
+    Align, ContributionGrid, WEEKS, crop_to_content, offset_columns, pad_columns, pad_rows,
+};
+
+const IMAGE: [[u8; 7]; 3] = [[1; 7], [2; 7], [3; 7]];
+
+fn grid(columns: &[[u8; 7]]) -> ContributionGrid {
+    ContributionGrid::from(columns.to_vec())
+}
+
+#[test]
+fn no_offset_is_a_no_op() {
+    assert_eq!(
+        offset_columns(&grid(&IMAGE), None, None, WEEKS)
+            .unwrap()
+            .cells,
+        IMAGE
+    );
+}
+
+#[test]
+fn explicit_offset() {
+    let columns = offset_columns(&grid(&IMAGE), Some(2), None, WEEKS).unwrap();
+    assert_eq!(columns.cells, [[0; 7], [0; 7], [1; 7], [2; 7], [3; 7]]);
+}
+
+#[test]
+fn alignment() {
+    let weeks = usize::from(WEEKS);
+    let center = offset_columns(&grid(&IMAGE), None, Some(Align::Center), WEEKS).unwrap();
+    assert_eq!(center.cols, (weeks - 3) / 2 + 3);
+    let right = offset_columns(&grid(&IMAGE), None, Some(Align::Right), WEEKS).unwrap();
+    assert_eq!(right.cols, weeks);
+    assert_eq!(right.cells.last(), Some(&vec![3; 7]));
+    // On a narrower graph, right-aligned is a column further left:
+    let narrower = offset_columns(&grid(&IMAGE), None, Some(Align::Right), WEEKS - 1).unwrap();
+    assert_eq!(narrower.cols, weeks - 1);
+    assert_eq!(narrower.cells.last(), Some(&vec![3; 7]));
+}
+
+#[test]
+fn overflow_is_an_error() {
+    let weeks = usize::from(WEEKS);
+    assert!(offset_columns(&grid(&IMAGE), Some(weeks - 3), None, WEEKS).is_ok());
+    assert!(offset_columns(&grid(&IMAGE), Some(weeks - 2), None, WEEKS).is_err());
+}
+
+#[test]
+fn cropping_drops_blank_columns_on_both_sides() {
+    let padded = grid(&[
+        [0; 7],
+        [0, 0, 0, 5, 0, 0, 0],
+        [0; 7],
+        [1; 7],
+        [0; 7],
+        [0; 7],
+    ]);
+    assert_eq!(
+        crop_to_content(&padded).cells,
+        [[0, 0, 0, 5, 0, 0, 0], [0; 7], [1; 7]],
+    );
+    assert_eq!(crop_to_content(&grid(&IMAGE)).cells, IMAGE);
+    let blank = crop_to_content(&grid(&[[0; 7]; 4]));
+    assert_eq!((blank.cols, blank.rows), (0, 7));
+    // Then centered as if the border had never been there:
+    let centered =
+        offset_columns(&crop_to_content(&padded), None, Some(Align::Center), WEEKS).unwrap();
+    assert_eq!(centered.cols, (usize::from(WEEKS) - 3) / 2 + 3);
+}
+
+#[test]
+fn padding_adds_blank_columns_and_rows() {
+    assert_eq!(
+        pad_columns(&grid(&IMAGE), 1, 2).cells,
+        [[0; 7], [1; 7], [2; 7], [3; 7], [0; 7], [0; 7]],
+    );
+    let mut columns = grid(&IMAGE);
+    let () = pad_rows(&mut columns, 2, 1);
+    assert_eq!(columns.cells[0], [0, 0, 1, 1, 1, 1, 0]);
+    let () = pad_rows(&mut columns, 9, 0);
+    assert_eq!(columns.cells[2], [0; 7]);
+}
@@ -0,0 +1,73 @@
+use contributron::run_cli;
+
+#[test]
+fn one_row_per_planned_commit() {
+    let dir = tempfile::tempdir().unwrap();
+    let image = dir.path().join("image.pgm");
+    let () = image::GrayImage::from_pixel(1, 7, image::Luma([255]))
+        .save(&image)
+        .unwrap();
+    let csv = dir.path().join("plan.csv");
+    let repo = dir.path().join("repo");
+    let () = run_cli([
+        "contributron",
+        "--repo",
+        repo.to_str().unwrap(),
+        "--image",
+        image.to_str().unwrap(),
+        "--name",
+        "Test",
+        "--email",
+        "test@example.com",
+        "--scale",
+        "2",
+        "--start-date",
+        "2024-03-10",
+        "--end-date",
+        "2024-03-11",
+        "--message",
+        "{date}, #{index}",
+        "--dry-run",
+        "--output-csv",
+        csv.to_str().unwrap(),
+    ])
+    .unwrap();
+    assert!(!repo.exists());
+    assert_eq!(
+        std::fs::read_to_string(&csv).unwrap(),
+        "date,commit_index,total_for_day,message\n\
+         2024-03-10,1,2,\"2024-03-10, #1\"\n\
+         2024-03-10,2,2,\"2024-03-10, #2\"\n\
+         2024-03-11,1,2,\"2024-03-11, #1\"\n\
+         2024-03-11,2,2,\"2024-03-11, #2\"\n",
+    );
+}
+
+#[test]
+fn since_rounds_down_to_sunday() {
+    let dir = tempfile::tempdir().unwrap();
+    let csv = dir.path().join("plan.csv");
+    let () = run_cli([
+        "contributron",
+        "--repo",
+        dir.path().join("repo").to_str().unwrap(),
+        "--pattern",
+        "solid",
+        "--name",
+        "Test",
+        "--email",
+        "test@example.com",
+        "--since",
+        "2024-03-12",
+        "--end-date",
+        "2024-03-11",
+        "--dry-run",
+        "--output-csv",
+        csv.to_str().unwrap(),
+    ])
+    .unwrap();
+    let csv = std::fs::read_to_string(&csv).unwrap();
+    let mut dates: Vec<_> = csv.lines().skip(1).map(|line| &line[..10]).collect();
+    let () = dates.dedup();
+    assert_eq!(dates, ["2024-03-10", "2024-03-11"]);
+}
@@ -0,0 +1,175 @@
+#![cfg(feature = "output-json")]
+
+use contributron::{
+    ContributronError, count_commits_per_day, read_checkpoint, read_plan, run_cli, write_checkpoint,
+};
+
+#[test]
+fn plan_round_trips_through_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let image = dir.path().join("image.pgm");
+    let () = image::GrayImage::from_fn(2, 7, |x, y| image::Luma([(40 * (x + y)) as u8]))
+        .save(&image)
+        .unwrap();
+    let json = dir.path().join("plan.json");
+    let repo = dir.path().join("repo");
+    let common = ["--name", "Test", "--email", "test@example.com", "--quiet"];
+
+    let () = run_cli(
+        [
+            "contributron",
+            "--repo",
+            repo.to_str().unwrap(),
+            "--image",
+            image.to_str().unwrap(),
+            "--end-date",
+            "2024-06-01",
+            "--dry-run",
+            "--output-json",
+            json.to_str().unwrap(),
+        ]
+        .into_iter()
+        .chain(common),
+    )
+    .unwrap();
+    assert!(!repo.exists());
+    let plan = read_plan(&json).unwrap();
+    assert_eq!(plan.version, env!("CARGO_PKG_VERSION"));
+    assert!(plan.cli_args.contains("2024-06-01"), "{}", plan.cli_args);
+    let total: usize = plan.entries.iter().map(|entry| entry.messages.len()).sum();
+    assert!(total > 0);
+
+    // Replay it without the image:
+    let () = run_cli(
+        [
+            "contributron",
+            "--repo",
+            repo.to_str().unwrap(),
+            "--from-plan",
+            json.to_str().unwrap(),
+        ]
+        .into_iter()
+        .chain(common),
+    )
+    .unwrap();
+    let repo = git2::Repository::open(&repo).unwrap();
+    let mut revwalk = repo.revwalk().unwrap();
+    let () = revwalk.push_head().unwrap();
+    assert_eq!(revwalk.count(), total);
+}
+
+#[test]
+fn resume_picks_up_after_the_checkpoint() {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = dir.path().join("repo");
+    let checkpoint = dir.path().join("checkpoint.json");
+    let run_with = |end: &str, extra: &[&str]| {
+        let argv = [
+            "contributron",
+            "generate",
+            "--repo",
+            repo.to_str().unwrap(),
+            "--pattern",
+            "solid",
+            "--name",
+            "Test",
+            "--email",
+            "test@example.com",
+            "--scale",
+            "1",
+            "--start-date",
+            "2024-03-10",
+            "--end-date",
+            end,
+            "--checkpoint",
+            checkpoint.to_str().unwrap(),
+            "--quiet",
+        ];
+        run_cli(argv.iter().chain(extra))
+    };
+    // Pretend the first run was interrupted after two days:
+    let () = run_with("2024-03-11", &[]).unwrap();
+    let saved = read_checkpoint(&checkpoint).unwrap();
+    assert_eq!(saved.last_completed_date.to_string(), "2024-03-11");
+    let git = git2::Repository::open(&repo).unwrap();
+    let head = git.head().unwrap().peel_to_commit().unwrap().id();
+    assert_eq!(saved.head_oid, head.to_string());
+    let () = run_with("2024-03-13", &["--resume"]).unwrap();
+    let dates: Vec<_> = count_commits_per_day(&git2::Repository::open(&repo).unwrap(), "HEAD")
+        .unwrap()
+        .into_values()
+        .collect();
+    assert_eq!(dates, [1, 1, 1, 1]);
+    assert_eq!(
+        read_checkpoint(&checkpoint)
+            .unwrap()
+            .last_completed_date
+            .to_string(),
+        "2024-03-13",
+    );
+
+    // Once the history has moved on, resuming needs `--force-resume`:
+    let () = write_checkpoint(&checkpoint, &saved).unwrap();
+    let Err(ContributronError::CheckpointDiverged(_)) = run_with("2024-03-14", &["--resume"])
+    else {
+        panic!("resumed onto a diverged history");
+    };
+    let () = run_with("2024-03-14", &["--resume", "--force-resume"]).unwrap();
+    assert_eq!(
+        count_commits_per_day(&git2::Repository::open(&repo).unwrap(), "HEAD")
+            .unwrap()
+            .len(),
+        5,
+    );
+
+    // A checkpoint would point to commits that `--in-memory` hasn't written yet:
+    let result = run_with("2024-03-15", &["--resume", "--in-memory"]);
+    assert!(
+        matches!(result, Err(ContributronError::InvalidArgCombination(_))),
+        "{result:?}",
+    );
+}
+
+#[test]
+fn poisson_commits_exactly_the_plan_previewed() {
+    let dir = tempfile::tempdir().unwrap();
+    let json = dir.path().join("plan.json");
+    let repo = dir.path().join("repo");
+    // No `--seed`, so only one draw of the counts can agree with the repository:
+    let () = run_cli([
+        "contributron",
+        "--repo",
+        repo.to_str().unwrap(),
+        "--pattern",
+        "solid",
+        "--poisson",
+        "--scale",
+        "4",
+        "--end-date",
+        "2024-03-30",
+        "--name",
+        "Test",
+        "--email",
+        "test@example.com",
+        "--output-json",
+        json.to_str().unwrap(),
+        "--preview",
+        "--quiet",
+    ])
+    .unwrap();
+    let plan = read_plan(&json).unwrap();
+    let committed = count_commits_per_day(&git2::Repository::open(&repo).unwrap(), "HEAD").unwrap();
+    let grid = contributron::plan_grid(&plan, contributron::WeekStart::Sunday);
+    for entry in &plan.entries {
+        let already = committed.get(&entry.date).copied().unwrap_or(0);
+        assert_eq!(already, u32::from(entry.commit_count), "{}", entry.date);
+    }
+    assert_eq!(
+        grid.cells
+            .iter()
+            .flatten()
+            .map(|&count| u32::from(count))
+            .sum::<u32>(),
+        committed.values().sum::<u32>(),
+    );
+}
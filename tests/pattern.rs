@@ -0,0 +1,174 @@
+use contributron::{
+    BuiltinKind, BuiltinPattern, ContributronError, NoisePattern, PatternSource, PerDayPattern,
+    Platform, WEEKS, WeekStart, builtin_pattern, qr_to_columns, run_cli,
+};
+
+#[test]
+fn builtin_patterns_span_the_graph() {
+    let columns = BuiltinPattern {
+        kind: BuiltinKind::Checkerboard,
+        brightness: 200,
+        period: 1,
+        seed: 0,
+        weeks: WEEKS,
+    }
+    .columns()
+    .cells;
+    assert_eq!(columns.len(), usize::from(WEEKS));
+    assert_eq!(columns[0], [200, 0, 200, 0, 200, 0, 200]);
+    assert_eq!(columns[1], [0, 200, 0, 200, 0, 200, 0]);
+    let columns = builtin_pattern(BuiltinKind::StripesV, 255, 1, 0, WEEKS).cells;
+    assert_eq!(columns[0], [255; 7]);
+    assert_eq!(columns[1], [0; 7]);
+    assert_eq!(
+        builtin_pattern(
+            BuiltinKind::Solid,
+            255,
+            1,
+            0,
+            Platform::Gitea.config().weeks
+        )
+        .cols,
+        52,
+    );
+}
+
+#[test]
+fn period_widens_stripes() {
+    let columns = builtin_pattern(BuiltinKind::StripesH, 255, 2, 0, WEEKS).cells;
+    assert_eq!(columns[0], [255, 255, 0, 0, 255, 255, 0]);
+    let columns = builtin_pattern(BuiltinKind::Diagonal, 255, 2, 0, WEEKS).cells;
+    assert_eq!(columns[0], [255, 255, 0, 0, 255, 255, 0]);
+    assert_eq!(columns[1], [255, 0, 0, 255, 255, 0, 0]);
+    let columns = builtin_pattern(BuiltinKind::Checkerboard, 255, 2, 0, WEEKS).cells;
+    assert_eq!(columns[1], [255, 255, 0, 0, 255, 255, 0]);
+    assert_eq!(columns[2], [0, 0, 255, 255, 0, 0, 255]);
+}
+
+#[test]
+fn random_pattern_is_reproducible() {
+    let random = |seed| builtin_pattern(BuiltinKind::Random, 255, 1, seed, WEEKS);
+    assert_eq!(random(7), random(7));
+    assert_ne!(random(7), random(8));
+}
+
+#[test]
+fn noise_is_reproducible_and_smooth() {
+    let noise = |density, seed| {
+        NoisePattern {
+            density,
+            seed,
+            weeks: WEEKS,
+        }
+        .columns()
+        .cells
+    };
+    assert_eq!(noise(0.1, 42), noise(0.1, 42));
+    assert_ne!(noise(0.1, 42), noise(0.1, 43));
+    assert_eq!(noise(0.1, 42).len(), usize::from(WEEKS));
+    // At a low frequency, neighboring cells are similar:
+    for pair in noise(0.1, 42).windows(2) {
+        for (&a, &b) in pair[0].iter().zip(&pair[1]) {
+            assert!(a.abs_diff(b) < 64, "{a} vs. {b}");
+        }
+    }
+    // And at a high one, not so much:
+    let grain = noise(2., 42);
+    assert!(
+        grain
+            .windows(2)
+            .any(|pair| pair[0][0].abs_diff(pair[1][0]) >= 64),
+        "{grain:?}",
+    );
+}
+
+#[test]
+fn qr_codes_shrink_to_seven_by_seven() {
+    let columns = qr_to_columns("https://example.com", WEEKS).unwrap().cells;
+    assert_eq!(columns.len(), 7);
+    assert!(
+        columns
+            .iter()
+            .flatten()
+            .all(|&pixel| pixel == 0 || pixel == 255),
+        "{columns:?}",
+    );
+    // Every QR code has a solid finder pattern in its top-left corner:
+    assert_eq!(columns[0][0], 255);
+    let result = qr_to_columns(&"x".repeat(1_000), WEEKS);
+    assert!(
+        matches!(result, Err(ContributronError::QrEncodingFailed(_))),
+        "{result:?}",
+    );
+}
+
+#[test]
+fn per_day_file_sets_each_days_count() {
+    let dir = tempfile::tempdir().unwrap();
+    let per_day = dir.path().join("per-day.csv");
+    let () = std::fs::write(
+        &per_day,
+        "date,count\n2024-03-12,4\n2024-03-10,2\n2025-01-01,9\n",
+    )
+    .unwrap();
+    let pattern = PerDayPattern::load(&per_day, WeekStart::Sunday).unwrap();
+    assert_eq!(pattern.busiest(), 9);
+    let date = |day: &str| day.parse().unwrap();
+    assert_eq!(pattern.brightness(date("2024-03-12"), 9), 113);
+    assert_eq!(pattern.brightness(date("2024-03-11"), 9), 0);
+    // From the Sunday before the first day through the last, relative to the busiest (9):
+    let columns = pattern.columns().cells;
+    assert_eq!(columns.len(), 43);
+    assert_eq!(columns[0], [56, 0, 113, 0, 0, 0, 0]);
+    assert_eq!(columns[42], [0, 0, 0, 255, 0, 0, 0]);
+    // Or from the Monday before, with the first Sunday ending its week:
+    let columns = PerDayPattern::load(&per_day, WeekStart::Monday)
+        .unwrap()
+        .columns()
+        .cells;
+    assert_eq!(columns.len(), 44);
+    assert_eq!(columns[0], [0, 0, 0, 0, 0, 0, 56]);
+    assert_eq!(columns[1], [0, 113, 0, 0, 0, 0, 0]);
+    assert_eq!(columns[43], [0, 0, 255, 0, 0, 0, 0]);
+
+    let csv = dir.path().join("plan.csv");
+    let () = run_cli([
+        "contributron",
+        "plan",
+        "--repo",
+        dir.path().join("repo").to_str().unwrap(),
+        "--per-day-file",
+        per_day.to_str().unwrap(),
+        "--name",
+        "Test",
+        "--email",
+        "test@example.com",
+        "--start-date",
+        "2024-03-10",
+        "--end-date",
+        "2024-03-13",
+        "--output-csv",
+        csv.to_str().unwrap(),
+    ])
+    .unwrap();
+    let csv = std::fs::read_to_string(&csv).unwrap();
+    let mut days: Vec<_> = csv
+        .lines()
+        .skip(1)
+        .map(|line| {
+            let mut fields = line.split(',');
+            (
+                fields.next().unwrap().to_owned(),
+                fields.nth(1).unwrap().to_owned(),
+            )
+        })
+        .collect();
+    let () = days.dedup();
+    assert_eq!(
+        days,
+        [
+            ("2024-03-10".to_owned(), "2".to_owned()),
+            ("2024-03-12".to_owned(), "4".to_owned()),
+        ],
+    );
+}
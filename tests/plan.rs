@@ -0,0 +1,142 @@
+use {
+    chrono::NaiveDate,
+    contributron::{
+        ContributionGrid, GitInfo, commit_count_table, draw_repeating_pattern, init_repo, plan,
+        statistics,
+    },
+};
+
+fn git(dir: &tempfile::TempDir) -> GitInfo<'static, 'static, 'static> {
+    GitInfo {
+        repo: init_repo(&dir.path().join("repo"), false).unwrap(),
+        reference: "HEAD",
+        name: "Test",
+        email: "test@example.com",
+        author_name: "Test",
+        author_email: "test@example.com",
+        dry_run: false,
+        offset_minutes: 0,
+        gpg: None,
+        quiet: true,
+        messages: vec!["{date} #{index}/{total}".to_owned()],
+        commits_made: std::cell::Cell::new(0),
+        commit_time: chrono::NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+        spread_hours: None,
+        gaussian_minutes: None,
+        jitter_secs: 0,
+        rng: None,
+        min_commits: 1,
+        max_commits: None,
+        poisson: false,
+        verbose: false,
+        content_file: None,
+        readme_template: None,
+        image_name: String::new(),
+        checkpoint: None,
+    }
+}
+
+#[test]
+fn plan_matches_what_gets_drawn() {
+    let dir = tempfile::tempdir().unwrap();
+    let git = git(&dir);
+    let start = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let end = NaiveDate::from_ymd_opt(2024, 3, 23).unwrap();
+    let columns = ContributionGrid::from(vec![[0, 64, 128, 192, 255, 255, 0]]);
+
+    let plan = plan(&columns, start..=end, 4, None, &git).unwrap();
+    assert_eq!(plan.entries.len(), 14);
+    assert_eq!(plan.entries[0].date, start);
+    assert_eq!(plan.entries[13].date, end);
+    let counts: Vec<u8> = plan
+        .entries
+        .iter()
+        .map(|entry| entry.commit_count)
+        .collect();
+    assert_eq!(counts, [0, 1, 2, 3, 4, 4, 0, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(
+        plan.entries[2].messages,
+        ["2024-03-12 #1/2", "2024-03-12 #2/2"]
+    );
+    // Planning makes no commits:
+    assert!(git.repo.head().is_err());
+
+    let () = draw_repeating_pattern(
+        &git,
+        &columns,
+        start..=end,
+        4,
+        None,
+        #[cfg(feature = "progress")]
+        None,
+    )
+    .unwrap();
+    let mut drawn = vec![];
+    let mut commit = git.repo.head().unwrap().peel_to_commit().ok();
+    while let Some(some) = commit {
+        drawn.push(some.message().unwrap().to_owned());
+        commit = some.parent(0).ok();
+    }
+    let () = drawn.reverse();
+    let planned: Vec<String> = plan
+        .entries
+        .into_iter()
+        .flat_map(|entry| entry.messages)
+        .collect();
+    assert_eq!(drawn, planned);
+}
+
+#[test]
+fn only_the_first_seven_rows_are_planned() {
+    let dir = tempfile::tempdir().unwrap();
+    let git = git(&dir);
+    let start = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let end = NaiveDate::from_ymd_opt(2024, 3, 23).unwrap();
+    // Nine rows, then three (missing the rest):
+    let columns = ContributionGrid::from_columns(
+        vec![vec![255, 0, 0, 0, 0, 0, 0, 255, 255], vec![0, 0, 255]],
+        9,
+    );
+    let counts: Vec<u8> = plan(&columns, start..=end, 4, None, &git)
+        .unwrap()
+        .entries
+        .iter()
+        .map(|entry| entry.commit_count)
+        .collect();
+    assert_eq!(counts, [4, 0, 0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0, 0]);
+}
+
+#[test]
+fn statistics_summarize_the_plan() {
+    let dir = tempfile::tempdir().unwrap();
+    let git = git(&dir);
+    let start = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let end = NaiveDate::from_ymd_opt(2024, 3, 23).unwrap();
+    let columns = ContributionGrid::from(vec![[0, 64, 128, 192, 255, 255, 0]]);
+    let stats = statistics(&plan(&columns, start..=end, 4, None, &git).unwrap());
+    assert_eq!(stats.total_commits, 14);
+    assert_eq!(stats.active_days, 5);
+    assert_eq!(stats.total_days, 14);
+    assert_eq!(stats.max_commits_per_day, 4);
+    assert_eq!(stats.min_nonzero_commits_per_day, 1);
+    assert!((stats.mean_commits_per_active_day - 2.8).abs() < 1e-6);
+    assert_eq!(stats.histogram[..5], [9, 1, 1, 1, 2]);
+    assert_eq!(stats.histogram.iter().sum::<u32>(), 14);
+    assert_eq!(
+        stats.to_string(),
+        "14 commits on 5 of 14 days (1 to 4 per active day, 2.8 on average)",
+    );
+}
+
+#[test]
+fn commit_count_table_counts_days_by_commits() {
+    let dir = tempfile::tempdir().unwrap();
+    let git = git(&dir);
+    let start = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let end = NaiveDate::from_ymd_opt(2024, 3, 23).unwrap();
+    let columns = ContributionGrid::from(vec![[0, 64, 128, 192, 255, 255, 0]]);
+    assert_eq!(
+        commit_count_table(&plan(&columns, start..=end, 4, None, &git).unwrap()),
+        "0 commits: 9 days, 1 commit: 1 day, 2 commits: 1 day, 3 commits: 1 day, 4 commits: 2 days",
+    );
+}
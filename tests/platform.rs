@@ -0,0 +1,28 @@
+use contributron::{Platform, WeekStart, platform_table, run_cli};
+
+#[test]
+fn each_platform_has_about_a_year_of_weeks() {
+    for platform in [Platform::GitHub, Platform::GitLab] {
+        let config = platform.config();
+        assert_eq!(config.weeks, 53);
+        assert_eq!(config.days(), 371);
+    }
+    assert_eq!(Platform::Gitea.config().weeks, 52);
+    assert_eq!(Platform::Gitea.config().days(), 364);
+    assert_eq!(Platform::GitHub.config().week_start, WeekStart::Sunday);
+    assert_eq!(Platform::GitLab.config().week_start, WeekStart::Monday);
+    assert_eq!(Platform::Gitea.config().week_start, WeekStart::Sunday);
+}
+
+#[test]
+fn table_lists_every_platform() {
+    let table = platform_table();
+    let lines: Vec<_> = table.lines().collect();
+    assert_eq!(lines.len(), 1 + Platform::ALL.len());
+    assert!(lines[0].starts_with("platform"), "{table}");
+    assert_eq!(
+        lines[2].split_whitespace().collect::<Vec<_>>(),
+        ["gitlab", "53", "371", "monday"],
+    );
+    let () = run_cli(["contributron", "list-platforms"]).unwrap();
+}
@@ -0,0 +1,207 @@
+use contributron::{
+    AsciiStyle, ContributionGrid, WeekStart, render_columns, render_preview, run_cli,
+};
+
+#[test]
+fn plain_preview_uses_digits() {
+    let grid = ContributionGrid::from(vec![[0, 1, 2, 3, 4, 5, 6], [8, 8, 8, 8, 8, 8, 8]]);
+    assert_eq!(
+        render_preview(&grid, 8, false),
+        "04\n14\n14\n24\n24\n34\n34\n",
+    );
+}
+
+#[test]
+fn plain_preview_blacks_out_missing_cells() {
+    let mut grid = ContributionGrid::from(vec![[8; 7], [8; 7]]);
+    let () = grid.cells[1].truncate(3);
+    assert_eq!(
+        render_preview(&grid, 8, false),
+        "44\n44\n44\n40\n40\n40\n40\n",
+    );
+}
+
+#[test]
+fn ansi_preview_uses_blocks() {
+    let preview = render_preview(&ContributionGrid::from(vec![[8; 7]]), 8, true);
+    assert_eq!(preview.lines().count(), 7);
+    assert!(preview.contains("██"), "{preview:?}");
+    assert!(preview.contains('\x1b'), "{preview:?}");
+}
+
+#[test]
+fn svg_has_a_square_per_day() {
+    use {
+        chrono::NaiveDate,
+        contributron::{CommitEntry, CommitPlan, render_svg},
+    };
+
+    let start = NaiveDate::from_ymd_opt(2024, 4, 21).unwrap();
+    let entries = (0..21)
+        .map(|day| CommitEntry {
+            date: start + chrono::Days::new(day),
+            pixel: 0,
+            commit_count: (day % 5) as u8,
+            messages: vec![],
+            times: vec![],
+        })
+        .collect();
+    let svg = render_svg(
+        &CommitPlan {
+            version: String::new(),
+            entries,
+            generated_at: chrono::Utc::now(),
+            cli_args: String::new(),
+        },
+        WeekStart::Sunday,
+    );
+    assert!(svg.starts_with("<svg"), "{svg}");
+    assert_eq!(svg.matches("<rect").count(), 21);
+    assert!(
+        svg.contains("<title>2024-04-21: 0 commits</title>"),
+        "{svg}"
+    );
+    assert!(svg.contains("<title>2024-04-22: 1 commit</title>"), "{svg}");
+    assert!(svg.contains("fill=\"#216e39\""), "{svg}");
+    // April's label over the first week, and May's over the week starting May 5:
+    assert_eq!(svg.matches("</text>").count(), 2, "{svg}");
+    assert!(
+        svg.contains("x=\"0\" y=\"10\" fill=\"#767676\">Apr</text>"),
+        "{svg}"
+    );
+    assert!(
+        svg.contains("x=\"26\" y=\"10\" fill=\"#767676\">May</text>"),
+        "{svg}"
+    );
+}
+
+#[test]
+fn png_preview_has_gaps_between_squares() {
+    use {
+        chrono::NaiveDate,
+        contributron::{CommitEntry, CommitPlan, render_preview_png},
+        image::Rgba,
+    };
+
+    let start = NaiveDate::from_ymd_opt(2024, 4, 21).unwrap();
+    let entries = (0..14)
+        .map(|day| CommitEntry {
+            date: start + chrono::Days::new(day),
+            pixel: 0,
+            commit_count: u8::from(day == 8) * 4,
+            messages: vec![],
+            times: vec![],
+        })
+        .collect();
+    let plan = CommitPlan {
+        version: String::new(),
+        entries,
+        generated_at: chrono::Utc::now(),
+        cli_args: String::new(),
+    };
+    let img = render_preview_png(&plan, WeekStart::Sunday);
+    assert_eq!(img.dimensions(), (2 * 13 - 2, 7 * 13 - 2));
+    assert_eq!(*img.get_pixel(0, 0), Rgba([0xeb, 0xed, 0xf0, 0xff]));
+    assert_eq!(img.get_pixel(11, 0).0[3], 0);
+    // Day 8 is the second week's Monday:
+    assert_eq!(*img.get_pixel(13, 13), Rgba([0x21, 0x6e, 0x39, 0xff]));
+    // Starting weeks on Monday, the first Sunday ends a week of its own, and day 8 tops the third:
+    let img = render_preview_png(&plan, WeekStart::Monday);
+    assert_eq!(img.dimensions(), (3 * 13 - 2, 7 * 13 - 2));
+    assert_eq!(img.get_pixel(0, 0).0[3], 0);
+    assert_eq!(*img.get_pixel(0, 6 * 13), Rgba([0xeb, 0xed, 0xf0, 0xff]));
+    assert_eq!(*img.get_pixel(2 * 13, 0), Rgba([0x21, 0x6e, 0x39, 0xff]));
+}
+
+#[test]
+fn grid_renders_as_ascii() {
+    let grid = ContributionGrid::from(vec![
+        [0, 255, 0, 0, 0, 0, 0],
+        [64, 255, 0, 0, 0, 0, 200],
+        [255, 0, 0, 0, 0, 0, 0],
+    ]);
+    let blocks = ContributionGrid::to_ascii(&grid, AsciiStyle::Blocks);
+    assert_eq!(blocks.lines().next(), Some(" ▒█"));
+    assert_eq!(blocks.lines().count(), 7);
+    let digits = ContributionGrid::to_ascii(&grid, AsciiStyle::Digits);
+    assert_eq!(digits, "024\n440\n000\n000\n000\n000\n040\n");
+    let hex = ContributionGrid::to_ascii(&grid, AsciiStyle::Hex);
+    assert_eq!(hex.lines().next(), Some("00 40 ff"));
+    assert_eq!(hex.lines().nth(6), Some("00 c8 00"));
+    // Rows 0-3 and 4-6 of columns 0-1 and 2:
+    let braille = ContributionGrid::to_ascii(&grid, AsciiStyle::Braille);
+    assert_eq!(braille, "\u{2812}\u{2801}\n\u{2820}\u{2800}\n");
+}
+
+#[test]
+fn columns_table_labels_days_and_weeks() {
+    let grid = ContributionGrid::from(vec![[0, 1, 2, 3, 4, 5, 255], [16; 7]]);
+    let table = render_columns(&grid, WeekStart::Sunday);
+    let lines: Vec<_> = table.lines().collect();
+    assert_eq!(lines.len(), 8);
+    assert_eq!(lines[0], "     1  2");
+    assert_eq!(lines[1], "Sun 00 10");
+    assert_eq!(lines[7], "Sat ff 10");
+    let monday = render_columns(&grid, WeekStart::Monday);
+    assert!(monday.lines().nth(1).unwrap().starts_with("Mon"));
+    assert!(monday.lines().nth(7).unwrap().starts_with("Sun"));
+}
+
+#[test]
+fn print_columns_never_commits() {
+    let dir = tempfile::tempdir().unwrap();
+    let image = dir.path().join("image.pgm");
+    let () = image::GrayImage::from_pixel(1, 7, image::Luma([100]))
+        .save(&image)
+        .unwrap();
+    let repo = dir.path().join("repo");
+    let () = run_cli([
+        "contributron",
+        "generate",
+        "--repo",
+        repo.to_str().unwrap(),
+        "--image",
+        image.to_str().unwrap(),
+        "--name",
+        "Test",
+        "--email",
+        "test@example.com",
+        "--print-columns",
+    ])
+    .unwrap();
+    assert!(!repo.exists());
+}
+
+#[test]
+fn plan_grid_lays_out_only_the_days_planned() {
+    use {
+        chrono::NaiveDate,
+        contributron::{CommitEntry, CommitPlan, plan_grid},
+    };
+
+    // A Wednesday through the next Tuesday, skipping Saturday (as if already drawn):
+    let start = NaiveDate::from_ymd_opt(2024, 4, 24).unwrap();
+    let entries = (0..7)
+        .filter(|&day| day != 3)
+        .map(|day| CommitEntry {
+            date: start + chrono::Days::new(day),
+            pixel: 0,
+            commit_count: day as u8 + 1,
+            messages: vec![],
+            times: vec![],
+        })
+        .collect();
+    let plan = CommitPlan {
+        version: String::new(),
+        entries,
+        generated_at: chrono::Utc::now(),
+        cli_args: String::new(),
+    };
+    let grid = plan_grid(&plan, WeekStart::Sunday);
+    assert_eq!((grid.cols, grid.rows), (2, 7));
+    assert_eq!(grid.cells[0], [0, 0, 0, 1, 2, 3, 0]);
+    assert_eq!(grid.cells[1], [5, 6, 7, 0, 0, 0, 0]);
+    let grid = plan_grid(&plan, WeekStart::Monday);
+    assert_eq!(grid.cells[0], [0, 0, 1, 2, 3, 0, 5]);
+    assert_eq!(grid.cells[1], [6, 7, 0, 0, 0, 0, 0]);
+}
@@ -0,0 +1,57 @@
+#![cfg(feature = "proptest")]
+
+use {
+    chrono::NaiveDate,
+    contributron::{ContributionGrid, auto_contrast, format_message, posterize},
+    proptest::prelude::*,
+};
+
+/// Any mix of literal text, escaped braces, and valid placeholders.
+fn template() -> impl Strategy<Value = String> {
+    prop::collection::vec(
+        prop_oneof![
+            "[^{}]*",
+            Just("{{".to_owned()),
+            Just("}}".to_owned()),
+            Just("{index}".to_owned()),
+            Just("{total}".to_owned()),
+            Just("{date}".to_owned()),
+            Just("{iso8601}".to_owned()),
+            Just("{pixel}".to_owned()),
+        ],
+        0..16,
+    )
+    .prop_map(|pieces| pieces.concat())
+}
+
+proptest! {
+    #[test]
+    fn valid_templates_always_format(
+        template in template(),
+        index: u8,
+        total: u8,
+        pixel: u8,
+        days in 0_u64..100_000,
+    ) {
+        let date = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap() + chrono::Days::new(days);
+        let time = date.and_time(chrono::NaiveTime::MIN).and_utc().fixed_offset();
+        prop_assert!(format_message(&template, index, total, pixel, date, time).is_ok());
+    }
+
+    #[test]
+    fn auto_contrast_stretches_to_black_and_white(columns in prop::collection::vec(any::<[u8; 7]>(), 1..64)) {
+        let pixels = columns.as_flattened();
+        prop_assume!(pixels.iter().any(|&pixel| pixel != pixels[0]));
+        let mut columns = ContributionGrid::from(columns);
+        let () = auto_contrast(&mut columns);
+        let pixels = columns.cells.concat();
+        prop_assert_eq!(pixels.iter().min(), Some(&0));
+        prop_assert_eq!(pixels.iter().max(), Some(&255));
+    }
+
+    #[test]
+    fn posterize_stays_in_range(pixel: u8, levels: u8) {
+        let posterized = u16::from(posterize(pixel, levels));
+        prop_assert!(posterized <= 255);
+    }
+}
@@ -0,0 +1,62 @@
+use {
+    chrono::NaiveDate,
+    contributron::{ContributronError, GitInfo, commit_tree, draw_pixel, init_repo, push},
+};
+
+fn git(dir: &tempfile::TempDir) -> GitInfo<'static, 'static, 'static> {
+    GitInfo {
+        repo: init_repo(&dir.path().join("repo"), false).unwrap(),
+        reference: "HEAD",
+        name: "Test",
+        email: "test@example.com",
+        author_name: "Test",
+        author_email: "test@example.com",
+        dry_run: false,
+        offset_minutes: 0,
+        gpg: None,
+        quiet: true,
+        messages: vec![contributron::DEFAULT_MESSAGE.to_owned()],
+        commits_made: std::cell::Cell::new(0),
+        commit_time: chrono::NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+        spread_hours: None,
+        gaussian_minutes: None,
+        jitter_secs: 0,
+        rng: None,
+        min_commits: 1,
+        max_commits: None,
+        poisson: false,
+        verbose: false,
+        content_file: None,
+        readme_template: None,
+        image_name: String::new(),
+        checkpoint: None,
+    }
+}
+
+#[test]
+fn push_to_a_local_url() {
+    let dir = tempfile::tempdir().unwrap();
+    let remote = git2::Repository::init_bare(dir.path().join("remote.git")).unwrap();
+    let git = git(&dir);
+    let date = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let () = draw_pixel(&git, &commit_tree(&git).unwrap(), 128, 2, date).unwrap();
+
+    let url = remote.path().to_str().unwrap();
+    let () = push(&git.repo, git.reference, url, false).unwrap();
+
+    let local = git.repo.head().unwrap().target().unwrap();
+    let branch = git.repo.head().unwrap().name().unwrap().to_owned();
+    let pushed = remote.find_reference(&branch).unwrap().target().unwrap();
+    assert_eq!(local, pushed);
+}
+
+#[test]
+fn missing_remote_is_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let git = git(&dir);
+    let result = push(&git.repo, git.reference, "origin", false);
+    assert!(
+        matches!(result, Err(ContributronError::RemoteNotFound(ref remote)) if remote == "origin"),
+        "{result:?}",
+    );
+}
@@ -0,0 +1,36 @@
+use contributron::{Axis, ContributionGrid, reflect};
+
+const L: [[u8; 7]; 2] = [[1, 2, 3, 4, 5, 6, 7], [8, 0, 0, 0, 0, 0, 0]];
+
+#[test]
+fn horizontal_flip_reverses_columns() {
+    assert_eq!(
+        reflect(&ContributionGrid::from(L.to_vec()), Axis::Horizontal).cells,
+        [[8, 0, 0, 0, 0, 0, 0], [1, 2, 3, 4, 5, 6, 7]],
+    );
+}
+
+#[test]
+fn vertical_flip_reverses_each_column() {
+    assert_eq!(
+        reflect(&ContributionGrid::from(L.to_vec()), Axis::Vertical).cells,
+        [[7, 6, 5, 4, 3, 2, 1], [0, 0, 0, 0, 0, 0, 8]],
+    );
+}
+
+#[test]
+fn vertical_flip_spans_every_row() {
+    let tall = ContributionGrid::from_columns(vec![(1..=9).collect()], 9);
+    assert_eq!(
+        reflect(&tall, Axis::Vertical).cells,
+        [[9, 8, 7, 6, 5, 4, 3, 2, 1]],
+    );
+}
+
+#[test]
+fn flipping_twice_is_a_no_op() {
+    let grid = ContributionGrid::from(L.to_vec());
+    for axis in [Axis::Horizontal, Axis::Vertical] {
+        assert_eq!(reflect(&reflect(&grid, axis), axis), grid);
+    }
+}
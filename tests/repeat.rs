@@ -0,0 +1,44 @@
+use contributron::{ContributionGrid, WEEKS, repeat_columns};
+
+const IMAGE: [[u8; 7]; 2] = [[1; 7], [2; 7]];
+
+fn image() -> ContributionGrid {
+    ContributionGrid::from(IMAGE.to_vec())
+}
+
+#[test]
+fn zero_repeats_endlessly() {
+    assert_eq!(repeat_columns(&image(), 0, WEEKS).cells, IMAGE);
+}
+
+#[test]
+fn exact_copies_then_blank() {
+    let columns = repeat_columns(&image(), 2, WEEKS);
+    assert_eq!(columns.cols, usize::from(WEEKS));
+    assert_eq!(
+        columns.cells[..6],
+        [[1; 7], [2; 7], [0; 7], [1; 7], [2; 7], [0; 7]],
+    );
+    assert!(columns.cells[6..].iter().all(|column| *column == [0; 7]));
+}
+
+#[test]
+fn too_many_copies_are_clamped() {
+    let columns = repeat_columns(&image(), 100, WEEKS);
+    assert_eq!(columns.cols, usize::from(WEEKS));
+    let copies = columns
+        .cells
+        .iter()
+        .filter(|column| **column == [1; 7])
+        .count();
+    assert_eq!(copies, usize::from(WEEKS) / 3);
+    // 53 columns fit 17 copies (3 columns each, with the blank one), but 50 fit only 16:
+    let columns = repeat_columns(&image(), 100, 50);
+    assert_eq!(columns.cols, 50);
+    let copies = columns
+        .cells
+        .iter()
+        .filter(|column| **column == [1; 7])
+        .count();
+    assert_eq!(copies, 16);
+}
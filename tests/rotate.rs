@@ -0,0 +1,30 @@
+use {
+    contributron::{Rotation, image_to_columns, rotate},
+    image::{DynamicImage, GrayImage, Luma},
+};
+
+fn portrait() -> DynamicImage {
+    DynamicImage::ImageLuma8(GrayImage::from_fn(7, 3, |x, y| Luma([(10 * y + x) as u8])))
+}
+
+#[test]
+fn rotating_there_and_back_is_a_no_op() {
+    let img = portrait();
+    let round_trip = rotate(&rotate(&img, Rotation::Quarter), Rotation::ThreeQuarters);
+    assert_eq!(round_trip, img);
+    let round_trip = rotate(&rotate(&img, Rotation::Half), Rotation::Half);
+    assert_eq!(round_trip, img);
+}
+
+#[test]
+fn quarter_turn_makes_rows_into_columns() {
+    let rotated = rotate(&portrait(), Rotation::Quarter);
+    assert_eq!(
+        image_to_columns(&rotated).cells,
+        [
+            [20, 21, 22, 23, 24, 25, 26],
+            [10, 11, 12, 13, 14, 15, 16],
+            [0, 1, 2, 3, 4, 5, 6],
+        ],
+    );
+}
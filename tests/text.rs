@@ -0,0 +1,75 @@
+use contributron::{ContributronError, FONT_5X7, PatternSource, TextPattern, text_to_columns};
+
+#[test]
+fn letters_are_spaced_glyphs() {
+    let columns = TextPattern {
+        text: "HI".to_owned(),
+        brightness: 9,
+        spacing: 2,
+    }
+    .columns()
+    .cells;
+    assert_eq!(columns.len(), 5 + 2 + 5);
+    // The left edge of an `H` is solid, and its crossbar is its middle row:
+    assert_eq!(columns[0], [9; 7]);
+    assert_eq!(columns[1], [0, 0, 0, 9, 0, 0, 0]);
+    assert_eq!(columns[5], [0; 7]);
+    assert_eq!(columns[6], [0; 7]);
+    // And the middle of an `I` is solid:
+    assert_eq!(columns[9], [9; 7]);
+}
+
+#[test]
+fn unprintable_characters_are_question_marks() {
+    let render = |text: &str| {
+        TextPattern {
+            text: text.to_owned(),
+            brightness: 255,
+            spacing: 0,
+        }
+        .columns()
+    };
+    assert_eq!(render("é\n"), render("??"));
+    assert_eq!(FONT_5X7[0], [[false; 7]; 5]);
+}
+
+#[test]
+fn custom_fonts_work_too() {
+    let mut font = [[[false; 7]; 5]; 95];
+    font[usize::from(b'x' - b' ')][4] = [true; 7];
+    let columns = text_to_columns("xx", &font, 1, 3);
+    assert_eq!(
+        columns.cells,
+        [
+            [0; 7], [0; 7], [0; 7], [0; 7], [3; 7], // x
+            [0; 7], // spacing
+            [0; 7], [0; 7], [0; 7], [0; 7], [3; 7], // x
+        ],
+    );
+}
+
+#[test]
+fn text_options_need_text() {
+    let run_with = |extra: &[&str]| {
+        contributron::run_cli(
+            [
+                "contributron",
+                "-r",
+                "repo",
+                "-n",
+                "Test",
+                "-e",
+                "test@example.com",
+            ]
+            .iter()
+            .chain(extra),
+        )
+    };
+    for option in ["--spacing", "--letter-brightness"] {
+        let result = run_with(&["-i", "nonexistent.png", option, "2"]);
+        assert!(
+            matches!(result, Err(ContributronError::InvalidArgCombination(ref message)) if message.contains(option)),
+            "{result:?}",
+        );
+    }
+}
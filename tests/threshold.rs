@@ -0,0 +1,18 @@
+use contributron::{ContributionGrid, threshold_columns};
+
+#[test]
+fn a_gradient_is_cut_off_below_the_threshold() {
+    let mut columns = ContributionGrid::from(
+        (0..36)
+            .map(|x| core::array::from_fn(|y| (x * 7 + y) as u8))
+            .collect::<Vec<[u8; 7]>>(),
+    );
+    let () = threshold_columns(&mut columns, 100);
+    for (i, &pixel) in columns.cells.iter().flatten().enumerate() {
+        if i < 100 {
+            assert_eq!(pixel, 0, "at {i}");
+        } else {
+            assert_eq!(usize::from(pixel), i, "at {i}");
+        }
+    }
+}
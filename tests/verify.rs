@@ -0,0 +1,80 @@
+use contributron::{Platform, WEEKS, run_cli, verify_image};
+
+#[test]
+fn reports_on_a_drawable_image() {
+    let dir = tempfile::tempdir().unwrap();
+    let image = dir.path().join("image.png");
+    // One white column, one dim column (blacked out by the threshold), one black column:
+    let () = image::GrayImage::from_fn(3, 7, |x, _| image::Luma([[255, 20, 0][x as usize]]))
+        .save(&image)
+        .unwrap();
+    let report = verify_image(&image, 4, 50, WEEKS).unwrap();
+    assert_eq!((report.width, report.height), (3, 7));
+    assert_eq!(report.color_mode, "L8");
+    assert_eq!((report.min_luma, report.max_luma), (0, 255));
+    assert!((report.mean_luma - 85.).abs() < 1e-3);
+    assert_eq!(report.nonzero_pixels, 7);
+    assert_eq!(report.total_commits, 28);
+    assert!(report.warnings.is_empty(), "{:?}", report.warnings);
+    assert_eq!(
+        report.to_string(),
+        "Dimensions: 3x7\nColor mode: L8\nLuma: 0 to 255 (85.0 on average)\nLit pixels: 7 of 21\nCommits: 28 per copy of the image",
+    );
+    let () = run_cli([
+        "contributron",
+        "verify-image",
+        "--image",
+        image.to_str().unwrap(),
+    ])
+    .unwrap();
+}
+
+#[test]
+fn warns_about_problems() {
+    let dir = tempfile::tempdir().unwrap();
+    let image = dir.path().join("image.png");
+    let () = image::RgbaImage::from_pixel(60, 8, image::Rgba([255, 255, 255, 0]))
+        .save(&image)
+        .unwrap();
+    let report = verify_image(&image, 10, 0, WEEKS).unwrap();
+    assert_eq!(report.color_mode, "Rgba8");
+    assert_eq!(report.nonzero_pixels, 0);
+    assert_eq!(report.warnings.len(), 4, "{:?}", report.warnings);
+    assert!(
+        report.warnings[0].contains("8px tall"),
+        "{:?}",
+        report.warnings
+    );
+    assert!(
+        report.warnings[1].contains("60px wide"),
+        "{:?}",
+        report.warnings
+    );
+}
+
+#[test]
+fn width_depends_on_the_platform() {
+    let dir = tempfile::tempdir().unwrap();
+    let image = dir.path().join("image.png");
+    let () = image::GrayImage::from_pixel(53, 7, image::Luma([255]))
+        .save(&image)
+        .unwrap();
+    let github = verify_image(&image, 10, 0, Platform::GitHub.config().weeks).unwrap();
+    assert!(github.warnings.is_empty(), "{:?}", github.warnings);
+    let gitea = verify_image(&image, 10, 0, Platform::Gitea.config().weeks).unwrap();
+    assert_eq!(gitea.warnings.len(), 1, "{:?}", gitea.warnings);
+    assert!(
+        gitea.warnings[0].contains("only 52 weeks wide"),
+        "{:?}",
+        gitea.warnings
+    );
+    let () = run_cli([
+        "contributron",
+        "verify-image",
+        "--image",
+        image.to_str().unwrap(),
+        "--platform",
+        "gitea",
+    ])
+    .unwrap();
+}
@@ -0,0 +1,35 @@
+use contributron::{ContributronError, run_cli};
+
+#[test]
+fn watch_needs_an_image_and_a_way_to_redraw() {
+    let dir = tempfile::tempdir().unwrap();
+    let image = dir.path().join("image.pgm");
+    let () = image::GrayImage::from_pixel(1, 7, image::Luma([255]))
+        .save(&image)
+        .unwrap();
+    let repo = dir.path().join("repo");
+    let run_with = |extra: &[&str]| {
+        let argv = [
+            "contributron",
+            "generate",
+            "--repo",
+            repo.to_str().unwrap(),
+            "--name",
+            "Test",
+            "--email",
+            "test@example.com",
+            "--watch",
+        ];
+        run_cli(argv.iter().chain(extra))
+    };
+    let result = run_with(&["--image", image.to_str().unwrap()]);
+    assert!(
+        matches!(result, Err(ContributronError::InvalidArgCombination(_))),
+        "{result:?}",
+    );
+    let result = run_with(&["--pattern", "solid", "--dry-run"]);
+    assert!(
+        matches!(result, Err(ContributronError::InvalidArgCombination(_))),
+        "{result:?}",
+    );
+}
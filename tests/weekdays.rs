@@ -0,0 +1,104 @@
+use contributron::{ContributronError, run_cli};
+
+/// The number of commits on each day with any from 2024-03-10 (a Sunday) to 2024-03-16,
+/// drawing a single column of luma 100.
+fn drawn_days(extra: &[&str]) -> Result<Vec<(String, String)>, ContributronError> {
+    let dir = tempfile::tempdir().unwrap();
+    let image = dir.path().join("image.pgm");
+    let () = image::GrayImage::from_pixel(1, 7, image::Luma([100]))
+        .save(&image)
+        .unwrap();
+    let csv = dir.path().join("plan.csv");
+    let repo = dir.path().join("repo");
+    let argv = [
+        "contributron",
+        "plan",
+        "--repo",
+        repo.to_str().unwrap(),
+        "--image",
+        image.to_str().unwrap(),
+        "--name",
+        "Test",
+        "--email",
+        "test@example.com",
+        "--start-date",
+        "2024-03-10",
+        "--end-date",
+        "2024-03-16",
+        "--output-csv",
+        csv.to_str().unwrap(),
+    ];
+    let () = run_cli(argv.iter().chain(extra))?;
+    let mut days: Vec<_> = std::fs::read_to_string(&csv)
+        .unwrap()
+        .lines()
+        .skip(1)
+        .map(|line| {
+            let fields: Vec<_> = line.split(',').collect();
+            (fields[0][8..].to_owned(), fields[2].to_owned())
+        })
+        .collect();
+    let () = days.dedup();
+    Ok(days)
+}
+
+/// Just the days of `drawn_days`.
+fn days(extra: &[&str]) -> Vec<String> {
+    drawn_days(extra)
+        .unwrap()
+        .into_iter()
+        .map(|(day, _)| day)
+        .collect()
+}
+
+#[test]
+fn weekday_only_skips_weekends() {
+    assert_eq!(days(&["--weekday-only"]), ["11", "12", "13", "14", "15"]);
+}
+
+#[test]
+fn weekend_only_skips_weekdays() {
+    assert_eq!(days(&["--weekend-only"]), ["10", "16"]);
+}
+
+#[test]
+fn weekend_boost_brightens_weekends() {
+    // 100 of 255 at `--scale 10` rounds to 4 commits, and 200 to 8:
+    let boosted = drawn_days(&["--weekend-boost", "2"]).unwrap();
+    let totals: Vec<_> = boosted.iter().map(|(_, total)| total.as_str()).collect();
+    assert_eq!(totals, ["8", "4", "4", "4", "4", "4", "8"]);
+    // Capped at white:
+    let capped = drawn_days(&["--weekend-boost", "10"]).unwrap();
+    assert_eq!(capped[0].1, "10");
+    for invalid in ["0", "-1", "10.5", "NaN"] {
+        let Err(ContributronError::InvalidArgument(_)) =
+            drawn_days(&[&format!("--weekend-boost={invalid}")])
+        else {
+            panic!("accepted `--weekend-boost {invalid}`");
+        };
+    }
+}
+
+#[test]
+fn gitlab_weeks_start_on_monday() {
+    // 2024-03-10 rounds down to Monday 2024-03-04, so the image's column is that week:
+    assert_eq!(
+        days(&["--platform", "gitlab", "--weekday-only"]),
+        ["04", "05", "06", "07", "08"],
+    );
+    assert_eq!(
+        days(&["--week-start", "monday", "--weekend-only"]),
+        ["09", "10"],
+    );
+    // `--week-start` overrides the platform's:
+    assert_eq!(
+        days(&[
+            "--platform",
+            "gitlab",
+            "--week-start",
+            "sunday",
+            "--weekday-only"
+        ]),
+        ["11", "12", "13", "14", "15"],
+    );
+}
@@ -0,0 +1,49 @@
+use contributron::{Commands, parse_args, render_workflow, run_cli};
+
+#[test]
+fn workflow_runs_the_same_command_weekly() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir
+        .path()
+        .join(".github")
+        .join("workflows")
+        .join("contributron.yml");
+    let () = run_cli([
+        "contributron",
+        "generate",
+        "--repo",
+        "art",
+        "--pattern",
+        "checkerboard",
+        "--name",
+        "Test",
+        "--email",
+        "test@example.com",
+        "--timezone",
+        "-300",
+        "--append",
+        "--generate-workflow",
+        path.to_str().unwrap(),
+    ])
+    .unwrap();
+    let workflow = std::fs::read_to_string(&path).unwrap();
+    assert!(workflow.contains("cron: '0 9 * * 1'"), "{workflow}");
+    assert!(workflow.contains("uses: actions/checkout"), "{workflow}");
+    assert!(
+        workflow.contains("cargo install contributron"),
+        "{workflow}"
+    );
+
+    // Running the workflow's command writes the same workflow (so it has every argument):
+    let (_, command) = workflow.rsplit_once("contributron generate").unwrap();
+    let argv = ["contributron", "generate"]
+        .into_iter()
+        .chain(command.split_whitespace().filter(|&arg| arg != "\\"));
+    let Commands::Generate(generate) = parse_args(argv).unwrap() else {
+        panic!();
+    };
+    let args = contributron::Args::from(*generate);
+    assert!(args.append);
+    assert_eq!(args.timezone, -300);
+    assert_eq!(render_workflow(&args).unwrap(), workflow);
+}
@@ -0,0 +1,34 @@
+use {
+    chrono::NaiveDate,
+    contributron::{WeekStart, year_window},
+};
+
+fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn whole_weeks_covering_the_year() {
+    // January 1, 2024 was a Monday, and December 31 a Tuesday:
+    assert_eq!(
+        year_window(2024, WeekStart::Sunday).unwrap(),
+        ymd(2023, 12, 31)..=ymd(2025, 1, 4)
+    );
+    // January 1, 2023 was a Sunday, and December 31 a Sunday too:
+    assert_eq!(
+        year_window(2023, WeekStart::Sunday).unwrap(),
+        ymd(2023, 1, 1)..=ymd(2024, 1, 6)
+    );
+}
+
+#[test]
+fn monday_weeks_covering_the_year() {
+    assert_eq!(
+        year_window(2024, WeekStart::Monday).unwrap(),
+        ymd(2024, 1, 1)..=ymd(2025, 1, 5)
+    );
+    assert_eq!(
+        year_window(2023, WeekStart::Monday).unwrap(),
+        ymd(2022, 12, 26)..=ymd(2023, 12, 31)
+    );
+}